@@ -0,0 +1,158 @@
+extern crate clap;
+
+use chrono::NaiveDate;
+use clap::{App, Arg, ArgMatches, ErrorKind};
+use log::LevelFilter;
+use std::fmt::Display;
+use std::path::PathBuf;
+
+use crate::data::{self, ScheduleQuery, Teams, DEFAULT_BASE_URL, DEFAULT_HYDRATE, DEFAULT_SPORT_ID};
+use crate::fixtures::FixtureMode;
+use crate::logging::LEVELS;
+use crate::player::DEFAULT_PLAYER;
+
+pub const DEFAULT_FIXTURE_DIR: &str = "./fixtures";
+pub const DEFAULT_WINDOW_SIZE: (u32, u32) = (960, 540);
+
+pub struct Options {
+    pub(crate) config: Option<PathBuf>,
+    pub(crate) date: Option<NaiveDate>,
+    /// The size from the config file when `None`
+    pub(crate) window_size: Option<(u32, u32)>,
+    pub(crate) fullscreen: bool,
+    pub(crate) query: ScheduleQuery,
+    pub(crate) log_level: LevelFilter,
+    pub(crate) fixtures: Option<(PathBuf, FixtureMode)>,
+    pub(crate) player: String,
+    pub(crate) favorites: Teams,
+    pub(crate) only_favorites: bool,
+    /// Every team when empty
+    pub(crate) teams: Teams
+}
+
+impl Options {
+    pub fn from_args() -> Self {
+        let matches = App::new("MLB Game Viewer")
+            .version(env!("CARGO_PKG_VERSION"))
+            .arg(Arg::with_name("config")
+                .long("config")
+                .value_name("FILE")
+                .takes_value(true)
+                .help("Configuration file the options below override [default: config.toml in the per-user config directory]"))
+            .arg(Arg::with_name("date")
+                .long("date")
+                .value_name("YYYY-MM-DD")
+                .takes_value(true)
+                .validator(|value| check(data::parse_date(&value)))
+                .help("Day whose games are shown first [default: today at the league office]"))
+            .arg(Arg::with_name("team")
+                .long("team")
+                .value_name("TEAM")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .help("Lists only the games of this team, given by id or name, can be repeated"))
+            .arg(Arg::with_name("window")
+                .long("window")
+                .value_name("WxH")
+                .takes_value(true)
+                .validator(|value| check(parse_window_size(&value)))
+                .help("Size of the window in pixels [default: 960x540, or the size it had when last closed]"))
+            .arg(Arg::with_name("fullscreen")
+                .long("fullscreen")
+                .help("Covers the whole screen instead of opening a window"))
+            .arg(Arg::with_name("base-url")
+                .long("base-url")
+                .value_name("URL")
+                .takes_value(true)
+                .validator(|value| check(ScheduleQuery::new(&value, &[], DEFAULT_SPORT_ID)))
+                .help("Root of the stats api every request is sent to [default: http://statsapi.mlb.com/api/v1]"))
+            .arg(Arg::with_name("sport-id")
+                .long("sport-id")
+                .value_name("ID")
+                .takes_value(true)
+                .validator(|value| check(value.parse::<u32>()))
+                .help("Sport whose schedule is shown, 1 is MLB, 11 to 14 are the minor league levels [default: 1]"))
+            .arg(Arg::with_name("log-level")
+                .long("log-level")
+                .value_name("LEVEL")
+                .takes_value(true)
+                .possible_values(&LEVELS)
+                .default_value("warn")
+                .help("Least severe messages written to stderr"))
+            .arg(Arg::with_name("fixtures")
+                .long("fixtures")
+                .value_name("DIR")
+                .takes_value(true)
+                .help("Directory holding recorded responses, replayed unless --record is given [default: ./fixtures]"))
+            .arg(Arg::with_name("record")
+                .long("record")
+                .help("Writes every fetched response to the fixture directory"))
+            .arg(Arg::with_name("offline")
+                .long("offline")
+                .conflicts_with("record")
+                .help("Serves every response from the fixture directory, never touching the network"))
+            .arg(Arg::with_name("player")
+                .long("player")
+                .value_name("COMMAND")
+                .takes_value(true)
+                .help("Command highlight videos are played with, the url is passed as its last argument [default: the system's url handler]"))
+            .arg(Arg::with_name("favorites")
+                .long("favorites")
+                .value_name("TEAMS")
+                .takes_value(true)
+                .help("Comma separated team ids or names whose games are listed first, as in 147,Red Sox, replacing the configured ones"))
+            .arg(Arg::with_name("only-favorites")
+                .long("only-favorites")
+                .help("Starts out listing only the favorite teams' games"))
+            .get_matches();
+
+        let fixture_dir = PathBuf::from(matches.value_of("fixtures").unwrap_or(DEFAULT_FIXTURE_DIR));
+        // A fixture directory on its own is replayed like --offline
+        let fixtures = if matches.is_present("record") {
+            Some((fixture_dir, FixtureMode::Record))
+        } else if matches.is_present("offline") || matches.is_present("fixtures") {
+            Some((fixture_dir, FixtureMode::Replay))
+        } else { None };
+
+        let favorites: Vec<&str> = matches.value_of("favorites").unwrap_or_default().split(',').collect();
+        let teams: Vec<&str> = matches.values_of("team").into_iter().flatten().collect();
+
+        Options {
+            config: matches.value_of("config").map(PathBuf::from),
+            date: matches.value_of("date").map(|value| data::parse_date(value).unwrap()),
+            window_size: matches.value_of("window").map(|value| parse_window_size(value).unwrap()),
+            fullscreen: matches.is_present("fullscreen"),
+            query: make_query(&matches),
+            log_level: matches.value_of("log-level").unwrap().parse().unwrap(),
+            fixtures,
+            player: matches.value_of("player").unwrap_or(DEFAULT_PLAYER).to_owned(),
+            favorites: Teams::new(&favorites),
+            only_favorites: matches.is_present("only-favorites"),
+            teams: Teams::new(&teams)
+        }
+    }
+}
+
+fn make_query(matches: &ArgMatches) -> ScheduleQuery {
+    let base_url = matches.value_of("base-url").unwrap_or(DEFAULT_BASE_URL);
+    let sport_id = matches.value_of("sport-id").map_or(DEFAULT_SPORT_ID, |value| value.parse().unwrap());
+    ScheduleQuery::new(base_url, &DEFAULT_HYDRATE, sport_id)
+        .unwrap_or_else(|err| clap::Error::with_description(err.to_string().as_str(), ErrorKind::InvalidValue).exit())
+}
+
+fn parse_window_size(value: &str) -> Result<(u32, u32), String> {
+    let invalid = || format!("'{}' is not a window size like 1280x720", value);
+    let split = value.find(['x', 'X']).ok_or_else(invalid)?;
+    let w: u32 = value[..split].trim().parse().map_err(|_| invalid())?;
+    let h: u32 = value[split + 1..].trim().parse().map_err(|_| invalid())?;
+    if w == 0 || h == 0 {
+        return Err(invalid());
+    }
+    Ok((w, h))
+}
+
+// Validators report why a value was rejected, clap prints it next to the option's name
+fn check<T, E: Display>(result: Result<T, E>) -> Result<(), String> {
+    result.map(|_| ()).map_err(|err| err.to_string())
+}
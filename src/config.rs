@@ -0,0 +1,491 @@
+extern crate toml;
+extern crate dirs;
+
+use sdl2::event::Event;
+use sdl2::keyboard::Keycode;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::cli::DEFAULT_WINDOW_SIZE;
+use crate::data::DEFAULT_LIVE_POLL_INTERVAL;
+use crate::http_cache::DEFAULT_MAX_BYTES;
+use crate::util::write_atomically;
+use crate::ui::{Color, Theme};
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Read(PathBuf, io::Error),
+    Parse(PathBuf, toml::de::Error),
+    Invalid(PathBuf, String)
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Read(path, err) => { write!(f, "could not read {}: {}", path.display(), err) }
+            ConfigError::Parse(path, err) => { write!(f, "{} is not valid toml: {}", path.display(), err) }
+            ConfigError::Invalid(path, reason) => { write!(f, "{}: {}", path.display(), reason) }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+// The file as written by the user, every section and key may be left out
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    window: WindowSection,
+    teams: TeamsSection,
+    theme: ThemeSection,
+    cache: CacheSection,
+    polling: PollingSection,
+    keys: KeysSection
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct WindowSection {
+    width: Option<u32>,
+    height: Option<u32>,
+    x: Option<i32>,
+    y: Option<i32>,
+    fullscreen: bool
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct TeamsSection {
+    favorites: Vec<String>,
+    only_favorites: bool
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct ThemeSection {
+    background: Option<PathBuf>,
+    text: Option<String>,
+    muted_text: Option<String>,
+    panel: Option<String>,
+    favorite: Option<String>
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct CacheSection {
+    enabled: Option<bool>,
+    dir: Option<PathBuf>,
+    max_megabytes: Option<u64>
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct PollingSection {
+    live_seconds: Option<u64>
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct KeysSection {
+    quit: Option<Vec<String>>,
+    open: Option<Vec<String>>,
+    back: Option<Vec<String>>,
+    retry: Option<Vec<String>>,
+    plays: Option<Vec<String>>,
+    standings: Option<Vec<String>>,
+    filter: Option<Vec<String>>,
+    watch: Option<Vec<String>>,
+    fullscreen: Option<Vec<String>>,
+    previous_day: Option<Vec<String>>,
+    next_day: Option<Vec<String>>
+}
+
+/// The keys triggering one action, any of them will do
+pub struct KeyBinding {
+    keys: Vec<Keycode>
+}
+
+impl KeyBinding {
+    pub fn new(keys: &[Keycode]) -> Self {
+        KeyBinding { keys: keys.to_vec() }
+    }
+
+    /// Parses SDL key names like "Return", "Page Up" or "F"
+    fn parse(action: &str, names: &[String]) -> Result<Self, String> {
+        let mut keys = Vec::new();
+        for name in names {
+            match Keycode::from_name(name.as_str()) {
+                Some(key) => { keys.push(key) }
+                None => { return Err(format!("'{}' bound to {} is not a key name", name, action)) }
+            }
+        }
+        if keys.is_empty() {
+            return Err(format!("{} needs at least one key", action));
+        }
+        Ok(KeyBinding { keys })
+    }
+
+    pub fn is_pressed(&self, event: &Event) -> bool {
+        match event {
+            Event::KeyDown { keycode: Some(key), .. } => { self.keys.contains(key) }
+            _ => { false }
+        }
+    }
+
+    /// The keys as shown in hints, as in "R or Enter"
+    pub fn describe(&self) -> String {
+        describe_keys(&[self])
+    }
+}
+
+/// The keys of all `bindings` as shown in hints, each key once, as in "S, Escape or Backspace"
+pub fn describe_keys(bindings: &[&KeyBinding]) -> String {
+    let mut names: Vec<String> = Vec::new();
+    for key in bindings.iter().flat_map(|binding| binding.keys.iter()) {
+        // SDL calls the enter key on the main keyboard "Return"
+        let name = if *key == Keycode::Return { "Enter".to_owned() } else { key.name() };
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    match names.split_last() {
+        Some((last, rest)) if !rest.is_empty() => { format!("{} or {}", rest.join(", "), last) }
+        Some((last, _)) => { last.clone() }
+        None => { String::new() }
+    }
+}
+
+pub struct KeyBindings {
+    pub(crate) quit: KeyBinding,
+    pub(crate) open: KeyBinding,
+    pub(crate) back: KeyBinding,
+    pub(crate) retry: KeyBinding,
+    pub(crate) plays: KeyBinding,
+    pub(crate) standings: KeyBinding,
+    pub(crate) filter: KeyBinding,
+    pub(crate) watch: KeyBinding,
+    pub(crate) fullscreen: KeyBinding,
+    pub(crate) previous_day: KeyBinding,
+    pub(crate) next_day: KeyBinding
+}
+
+impl KeyBindings {
+    /// The lists and scrolled views take the arrow and page keys before any action sees them.
+    /// The schedule leaves the vertical ones alone, so only the day navigation may use those.
+    fn check_navigation(&self) -> Result<(), String> {
+        let actions = [
+            ("quit", &self.quit), ("open", &self.open), ("back", &self.back), ("retry", &self.retry),
+            ("plays", &self.plays), ("standings", &self.standings), ("filter", &self.filter), ("watch", &self.watch),
+            ("fullscreen", &self.fullscreen), ("previous_day", &self.previous_day), ("next_day", &self.next_day)
+        ];
+        for (action, binding) in actions.iter() {
+            let day = *action == "previous_day" || *action == "next_day";
+            for key in &binding.keys {
+                let taken = match key {
+                    Keycode::Left | Keycode::Right => { true }
+                    Keycode::Up | Keycode::Down | Keycode::PageUp | Keycode::PageDown => { !day }
+                    _ => { false }
+                };
+                if taken {
+                    return Err(format!("keys.{} can not use {:?}, the lists and scrolled views move with it", action, key));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        KeyBindings {
+            quit: KeyBinding::new(&[Keycode::Escape]),
+            open: KeyBinding::new(&[Keycode::Return]),
+            back: KeyBinding::new(&[Keycode::Escape, Keycode::Backspace]),
+            retry: KeyBinding::new(&[Keycode::R, Keycode::Return]),
+            plays: KeyBinding::new(&[Keycode::P]),
+            standings: KeyBinding::new(&[Keycode::S]),
+            filter: KeyBinding::new(&[Keycode::F]),
+            watch: KeyBinding::new(&[Keycode::Space]),
+            fullscreen: KeyBinding::new(&[Keycode::F11]),
+            previous_day: KeyBinding::new(&[Keycode::Up, Keycode::PageUp]),
+            next_day: KeyBinding::new(&[Keycode::Down, Keycode::PageDown])
+        }
+    }
+}
+
+pub struct WindowGeometry {
+    pub(crate) size: (u32, u32),
+    /// Centered on the screen when `None`
+    pub(crate) position: Option<(i32, i32)>,
+    pub(crate) fullscreen: bool
+}
+
+pub struct CacheSettings {
+    pub(crate) enabled: bool,
+    /// The per-user cache directory when `None`
+    pub(crate) dir: Option<PathBuf>,
+    pub(crate) max_bytes: u64
+}
+
+/// The validated configuration, with defaults for everything the file left out
+pub struct Config {
+    pub(crate) window: WindowGeometry,
+    pub(crate) favorites: Vec<String>,
+    pub(crate) only_favorites: bool,
+    pub(crate) theme: Theme,
+    pub(crate) cache: CacheSettings,
+    pub(crate) live_poll_interval: Duration,
+    pub(crate) keys: KeyBindings
+}
+
+impl Config {
+    /// `$XDG_CONFIG_HOME/mlb_game_viewer/config.toml` on Linux
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("mlb_game_viewer").join("config.toml"))
+    }
+
+    /// Reads the file at `path`, a missing file is the same as an empty one
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => { text }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => { String::new() }
+            Err(err) => { return Err(ConfigError::Read(path.to_owned(), err)) }
+        };
+        let file: ConfigFile = toml::from_str(text.as_str()).map_err(|err| ConfigError::Parse(path.to_owned(), err))?;
+        Config::validate(file).map_err(|reason| ConfigError::Invalid(path.to_owned(), reason))
+    }
+
+    fn validate(file: ConfigFile) -> Result<Self, String> {
+        let ConfigFile { window, teams, theme, cache, polling, keys } = file;
+
+        let size = (window.width.unwrap_or(DEFAULT_WINDOW_SIZE.0), window.height.unwrap_or(DEFAULT_WINDOW_SIZE.1));
+        if size.0 == 0 || size.1 == 0 {
+            return Err("window.width and window.height must be greater than 0".to_owned());
+        }
+        let position = match (window.x, window.y) {
+            (Some(x), Some(y)) => { Some((x, y)) }
+            (None, None) => { None }
+            _ => { return Err("window.x and window.y must be given together".to_owned()) }
+        };
+
+        let default_theme = Theme::default();
+        let color = |key: &str, value: Option<String>, default: Color| match value {
+            Some(value) => { parse_color(value.as_str()).ok_or_else(|| format!("theme.{} '{}' is not a color like \"#ffc828\"", key, value)) }
+            None => { Ok(default) }
+        };
+        let theme = Theme {
+            background: theme.background.unwrap_or(default_theme.background),
+            text: color("text", theme.text, default_theme.text)?,
+            muted_text: color("muted_text", theme.muted_text, default_theme.muted_text)?,
+            panel: color("panel", theme.panel, default_theme.panel)?,
+            favorite: color("favorite", theme.favorite, default_theme.favorite)?
+        };
+
+        let max_bytes = match cache.max_megabytes {
+            Some(0) => { return Err("cache.max_megabytes must be greater than 0, set cache.enabled = false to turn the cache off".to_owned()) }
+            Some(megabytes) => { megabytes * 1024 * 1024 }
+            None => { DEFAULT_MAX_BYTES }
+        };
+
+        let live_poll_interval = match polling.live_seconds {
+            Some(0) => { return Err("polling.live_seconds must be greater than 0".to_owned()) }
+            Some(seconds) => { Duration::from_secs(seconds) }
+            None => { DEFAULT_LIVE_POLL_INTERVAL }
+        };
+
+        let defaults = KeyBindings::default();
+        let binding = |action: &str, names: Option<Vec<String>>, default: KeyBinding| match names {
+            Some(names) => { KeyBinding::parse(format!("keys.{}", action).as_str(), &names) }
+            None => { Ok(default) }
+        };
+        let keys = KeyBindings {
+            quit: binding("quit", keys.quit, defaults.quit)?,
+            open: binding("open", keys.open, defaults.open)?,
+            back: binding("back", keys.back, defaults.back)?,
+            retry: binding("retry", keys.retry, defaults.retry)?,
+            plays: binding("plays", keys.plays, defaults.plays)?,
+            standings: binding("standings", keys.standings, defaults.standings)?,
+            filter: binding("filter", keys.filter, defaults.filter)?,
+            watch: binding("watch", keys.watch, defaults.watch)?,
+            fullscreen: binding("fullscreen", keys.fullscreen, defaults.fullscreen)?,
+            previous_day: binding("previous_day", keys.previous_day, defaults.previous_day)?,
+            next_day: binding("next_day", keys.next_day, defaults.next_day)?
+        };
+        keys.check_navigation()?;
+
+        Ok(Config {
+            window: WindowGeometry { size, position, fullscreen: window.fullscreen },
+            favorites: teams.favorites,
+            only_favorites: teams.only_favorites,
+            theme,
+            cache: CacheSettings { enabled: cache.enabled.unwrap_or(true), dir: cache.dir, max_bytes },
+            live_poll_interval,
+            keys
+        })
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config::validate(ConfigFile::default()).unwrap()
+    }
+}
+
+/// The window's size and position when the viewer was last closed, kept in a file of its own
+/// so that saving it never touches the user's configuration
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct WindowState {
+    width: Option<u32>,
+    height: Option<u32>,
+    x: Option<i32>,
+    y: Option<i32>
+}
+
+impl WindowState {
+    pub fn new(size: Option<(u32, u32)>, position: (i32, i32)) -> Self {
+        WindowState {
+            width: size.map(|size| size.0),
+            height: size.map(|size| size.1),
+            x: Some(position.0),
+            y: Some(position.1)
+        }
+    }
+
+    /// `$XDG_DATA_HOME/mlb_game_viewer/window.toml` on Linux
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_local_dir().map(|dir| dir.join("mlb_game_viewer").join("window.toml"))
+    }
+
+    /// Reads the state saved at `path`, nothing was saved yet if the file is missing
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(text) => { toml::from_str(text.as_str()).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err)) }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => { Ok(WindowState::default()) }
+            Err(err) => { Err(err) }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text = toml::to_string(self).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        write_atomically(path, text.as_bytes())
+    }
+
+    pub fn size(&self) -> Option<(u32, u32)> {
+        match (self.width, self.height) {
+            (Some(width), Some(height)) if width > 0 && height > 0 => { Some((width, height)) }
+            _ => { None }
+        }
+    }
+
+    pub fn position(&self) -> Option<(i32, i32)> {
+        self.x.zip(self.y)
+    }
+}
+
+/// "#rrggbb" or "rrggbb"
+fn parse_color(value: &str) -> Option<Color> {
+    let hex = value.trim().trim_start_matches('#');
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |at: usize| u32::from_str_radix(&hex[at..at + 2], 16).ok();
+    Some(Color::new(channel(0)?, channel(2)?, channel(4)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(text: &str) -> Result<Config, String> {
+        Config::validate(toml::from_str(text).unwrap())
+    }
+
+    #[test]
+    fn accepts_an_empty_file() {
+        let config = validate("").unwrap();
+        assert_eq!(config.window.size, DEFAULT_WINDOW_SIZE);
+        assert!(config.window.position.is_none());
+        assert!(config.keys.previous_day.keys.contains(&Keycode::PageUp));
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        for text in [
+            "[window]\nwidth = 0",
+            "[window]\nx = 10",
+            "[theme]\ntext = \"white\"",
+            "[cache]\nmax_megabytes = 0",
+            "[polling]\nlive_seconds = 0",
+            "[keys]\nopen = []",
+            "[keys]\nopen = [\"Enter Key\"]"
+        ].iter() {
+            assert!(validate(text).is_err(), "{} was accepted", text);
+        }
+    }
+
+    #[test]
+    fn rejects_keys_the_lists_move_with() {
+        for text in [
+            "[keys]\nopen = [\"Right\"]",
+            "[keys]\nnext_day = [\"Left\"]",
+            "[keys]\nwatch = [\"Down\"]",
+            "[keys]\nback = [\"Escape\", \"PageUp\"]"
+        ].iter() {
+            assert!(validate(text).is_err(), "{} was accepted", text);
+        }
+    }
+
+    #[test]
+    fn lets_the_day_navigation_use_the_vertical_keys() {
+        let config = validate("[keys]\nprevious_day = [\"Down\"]\nnext_day = [\"Up\", \"PageUp\"]").unwrap();
+        assert_eq!(config.keys.previous_day.keys, vec![Keycode::Down]);
+        assert_eq!(config.keys.next_day.keys, vec![Keycode::Up, Keycode::PageUp]);
+    }
+
+    #[test]
+    fn describes_keys_for_hints() {
+        let keys = KeyBindings::default();
+        assert_eq!(keys.retry.describe(), "R or Enter");
+        assert_eq!(keys.watch.describe(), "Space");
+        assert_eq!(describe_keys(&[&keys.standings, &keys.back]), "S, Escape or Backspace");
+        // The quit key is also a back key
+        assert_eq!(describe_keys(&[&keys.quit, &keys.back]), "Escape or Backspace");
+    }
+
+    #[test]
+    fn parses_colors() {
+        let color = parse_color("#ffc828").unwrap();
+        assert_eq!((color.r, color.g, color.b), (255, 200, 40));
+        let color = parse_color(" 0A0b0C ").unwrap();
+        assert_eq!((color.r, color.g, color.b), (10, 11, 12));
+        for value in ["", "#fff", "#ffc82800", "ffc82g", "#ffc8é"].iter() {
+            assert!(parse_color(value).is_none(), "{} was parsed", value);
+        }
+    }
+
+    #[test]
+    fn saves_and_loads_the_window_state() {
+        let dir = std::env::temp_dir().join(format!("mlb_game_viewer_config_{}", std::process::id()));
+        let path = dir.join("window.toml");
+        assert!(WindowState::load(&path).unwrap().size().is_none());
+
+        WindowState::new(Some((1280, 720)), (-20, 40)).save(&path).unwrap();
+        let state = WindowState::load(&path).unwrap();
+        assert_eq!(state.size(), Some((1280, 720)));
+        assert_eq!(state.position(), Some((-20, 40)));
+
+        // The size given with --window is left out
+        WindowState::new(None, (0, 0)).save(&path).unwrap();
+        assert_eq!(WindowState::load(&path).unwrap().size(), None);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
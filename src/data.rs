@@ -1,100 +1,1395 @@
-extern crate json;
 extern crate curl;
+extern crate serde_json;
+extern crate chrono;
+extern crate chrono_tz;
 
-use curl::easy::Easy;
-use self::json::JsonValue;
+use curl::easy::{Easy, List};
+use log::{debug, warn};
+use serde::de::DeserializeOwned;
 use image::EncodableLayout;
+use chrono::{NaiveDate, DateTime, Local, Utc, Datelike};
+use chrono_tz::America::New_York;
+use std::fmt;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender, TryRecvError};
+use std::sync::{Arc, Mutex};
+use std::collections::{VecDeque, HashMap, HashSet};
+use std::thread;
+use std::time::Duration;
+use crate::http_cache::{HttpCache, Freshness};
+use crate::fixtures::Fixtures;
+use crate::statsapi;
+
+pub const DEFAULT_BASE_URL: &str = "http://statsapi.mlb.com/api/v1";
+pub const DEFAULT_HYDRATE: [&str; 3] = [
+    "game(content(editorial(recap),highlights(highlights)))",
+    // The season numbers of the pitchers credited with a decision
+    "decisions(person(stats(group=[pitching],type=[season])))",
+    "linescore"
+];
+pub const DEFAULT_SPORT_ID: u32 = 1;
+
+// The statsapi has no schedule data before the National League's first season
+const FIRST_SEASON: i32 = 1876;
+
+#[derive(Debug)]
+pub enum Error {
+    Transport(curl::Error),
+    HttpStatus { url: String, code: u32 },
+    Decode(String),
+    Schema(String),
+    FixtureMissing(String),
+    /// The worker fetching the answer went away without sending it
+    Stopped
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Transport(err) => { write!(f, "network error: {}", err) }
+            Error::HttpStatus { url, code } => { write!(f, "server answered {} for {}", code, url) }
+            Error::Decode(reason) => { write!(f, "could not decode response: {}", reason) }
+            Error::Schema(reason) => { write!(f, "unexpected response: {}", reason) }
+            Error::FixtureMissing(url) => { write!(f, "no recorded response for {} while offline", url) }
+            Error::Stopped => { write!(f, "the loader stopped unexpectedly") }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Transport(err) => { Some(err) }
+            _ => { None }
+        }
+    }
+}
+
+impl From<curl::Error> for Error {
+    fn from(err: curl::Error) -> Self {
+        Error::Transport(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        // Well formed json that does not match our structs means the api has drifted
+        match err.classify() {
+            serde_json::error::Category::Data => { Error::Schema(err.to_string()) }
+            _ => { Error::Decode(err.to_string()) }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GameStatus {
+    Scheduled,
+    PreGame,
+    InProgress,
+    Final,
+    Postponed,
+    Delayed
+}
+
+impl GameStatus {
+    fn from_status(status: &statsapi::GameStatus) -> Self {
+        let detailed = status.detailed_state.as_str();
+        match status.coded_game_state.as_str() {
+            // Postponed and cancelled games will not be played on this day at all
+            "D" | "C" => { return GameStatus::Postponed }
+            // Suspended games stopped for now and will resume on another day
+            "U" | "T" => { return GameStatus::Delayed }
+            _ => {}
+        }
+        if detailed.starts_with("Postponed") || detailed.starts_with("Cancelled") {
+            GameStatus::Postponed
+        } else if detailed.starts_with("Delayed") || detailed.starts_with("Suspended") {
+            GameStatus::Delayed
+        } else if detailed == "Pre-Game" || detailed == "Warmup" {
+            GameStatus::PreGame
+        } else {
+            match status.abstract_game_state.as_str() {
+                "Final" => { GameStatus::Final }
+                "Live" => { GameStatus::InProgress }
+                _ => { GameStatus::Scheduled }
+            }
+        }
+    }
+}
+
+impl fmt::Display for GameStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            GameStatus::Scheduled => { "Scheduled" }
+            GameStatus::PreGame => { "Pre-Game" }
+            GameStatus::InProgress => { "In Progress" }
+            GameStatus::Final => { "Final" }
+            GameStatus::Postponed => { "Postponed" }
+            GameStatus::Delayed => { "Delayed" }
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InningHalf {
+    Top,
+    Middle,
+    Bottom,
+    End
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Inning {
+    pub(crate) number: u32,
+    pub(crate) ordinal: String,
+    pub(crate) half: InningHalf
+}
+
+impl Inning {
+    fn from_linescore(linescore: &statsapi::GameLinescore) -> Option<Self> {
+        let number = linescore.current_inning?;
+        let half = match linescore.inning_half.as_deref()? {
+            "Top" => { InningHalf::Top }
+            "Middle" => { InningHalf::Middle }
+            "Bottom" => { InningHalf::Bottom }
+            "End" => { InningHalf::End }
+            _ => { return None }
+        };
+        Some(Inning {
+            number,
+            ordinal: linescore.current_inning_ordinal.clone().unwrap_or_else(|| number.to_string()),
+            half
+        })
+    }
+}
+
+impl fmt::Display for Inning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let half = match self.half {
+            InningHalf::Top => { "Top" }
+            InningHalf::Middle => { "Mid" }
+            InningHalf::Bottom => { "Bot" }
+            InningHalf::End => { "End" }
+        };
+        write!(f, "{} {}", half, self.ordinal)
+    }
+}
+
+/// One team's row of the linescore grid
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LinescoreRow {
+    // Indexed by inning, `None` for innings the team has not batted in
+    pub(crate) runs_by_inning: Vec<Option<u32>>,
+    pub(crate) runs: Option<u32>,
+    pub(crate) hits: Option<u32>,
+    pub(crate) errors: Option<u32>
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Linescore {
+    pub(crate) home: LinescoreRow,
+    pub(crate) away: LinescoreRow
+}
+
+impl Linescore {
+    fn from_linescore(linescore: &statsapi::GameLinescore) -> Self {
+        let row = |inning: fn(&statsapi::LinescoreInning) -> &statsapi::LinescoreLine, total: Option<&statsapi::LinescoreLine>| {
+            LinescoreRow {
+                runs_by_inning: linescore.innings.iter().map(|played| inning(played).runs).collect(),
+                runs: total.and_then(|total| total.runs),
+                hits: total.and_then(|total| total.hits),
+                errors: total.and_then(|total| total.errors)
+            }
+        };
+        let teams = linescore.teams.as_ref();
+
+        Linescore {
+            home: row(|inning| &inning.home, teams.map(|teams| &teams.home)),
+            away: row(|inning| &inning.away, teams.map(|teams| &teams.away))
+        }
+    }
+
+    /// The number of innings played or being played so far
+    pub fn innings(&self) -> usize {
+        self.home.runs_by_inning.len().max(self.away.runs_by_inning.len())
+    }
+}
+
+/// A highlight video that can be handed to a player
+#[derive(Clone, Debug, PartialEq)]
+pub struct Highlight {
+    pub(crate) title: String,
+    pub(crate) duration: Option<String>,
+    pub(crate) image_cuts: Vec<statsapi::ImageCut>,
+    pub(crate) url: String
+}
+
+impl Highlight {
+    /// Skips videos without any playback a player could open
+    fn from_media(media: &statsapi::MediaItem) -> Option<Self> {
+        Some(Highlight {
+            title: media.title.clone().unwrap_or_default(),
+            duration: media.duration.clone(),
+            image_cuts: media.image.as_ref().map(|image| image.cuts.clone()).unwrap_or_default(),
+            url: best_playback(&media.playbacks)?.url.clone()
+        })
+    }
+
+    pub fn best_cut(&self, target: (u32, u32), pixel_ratio: f32) -> Option<&statsapi::ImageCut> {
+        select_cut(&self.image_cuts, target, pixel_ratio)
+    }
+}
+
+/// Prefers the 720p MP4 encodings, then any other MP4, then HLS streams
+fn best_playback(playbacks: &[statsapi::Playback]) -> Option<&statsapi::Playback> {
+    playbacks.iter()
+        .filter_map(|playback| {
+            let path = playback.url.split('?').next().unwrap_or_default();
+            let rank = if playback.name == "mp4Avc" {
+                0
+            } else if playback.name == "highBit" {
+                1
+            } else if path.ends_with(".mp4") {
+                2
+            } else if path.ends_with(".m3u8") {
+                3
+            } else { return None };
+            Some((rank, playback))
+        })
+        .min_by_key(|(rank, _)| *rank)
+        .map(|(_, playback)| playback)
+}
+
+/// A pitcher credited with a decision, with their season numbers when the api included them
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pitcher {
+    pub(crate) name: String,
+    pub(crate) wins: Option<u32>,
+    pub(crate) losses: Option<u32>,
+    pub(crate) saves: Option<u32>,
+    pub(crate) era: Option<String>
+}
+
+impl Pitcher {
+    fn from_person(person: &statsapi::Person, season: &str) -> Self {
+        let stats = person.pitching_stats(season);
+        Pitcher {
+            name: person.full_name.clone(),
+            wins: stats.and_then(|stats| stats.wins),
+            losses: stats.and_then(|stats| stats.losses),
+            saves: stats.and_then(|stats| stats.saves),
+            era: stats.and_then(|stats| stats.era.clone())
+        }
+    }
+
+    /// The season record as in "10-4, 3.25 ERA", leaving out whatever is missing
+    pub fn record(&self) -> Option<String> {
+        let won_lost = match (self.wins, self.losses) {
+            (Some(wins), Some(losses)) => { Some(format!("{}-{}", wins, losses)) }
+            _ => { None }
+        };
+        let era = self.era.as_ref().map(|era| format!("{} ERA", era));
+        match (won_lost, era) {
+            (Some(won_lost), Some(era)) => { Some(format!("{}, {}", won_lost, era)) }
+            (won_lost, era) => { won_lost.or(era) }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Decisions {
+    pub(crate) winner: Option<Pitcher>,
+    pub(crate) loser: Option<Pitcher>,
+    pub(crate) save: Option<Pitcher>
+}
+
+impl Decisions {
+    fn from_decisions(decisions: &statsapi::Decisions, season: &str) -> Self {
+        let pitcher = |person: &statsapi::Person| Pitcher::from_person(person, season);
+        Decisions {
+            winner: decisions.winner.as_ref().map(pitcher),
+            loser: decisions.loser.as_ref().map(pitcher),
+            save: decisions.save.as_ref().map(pitcher)
+        }
+    }
+}
 
 pub struct GameModel {
+    pub(crate) game_pk: u64,
+    pub(crate) status: GameStatus,
     pub(crate) home_team: String,
     pub(crate) away_team: String,
+    pub(crate) home_team_id: u64,
+    pub(crate) away_team_id: u64,
+    pub(crate) home_runs: Option<u32>,
+    pub(crate) away_runs: Option<u32>,
+    pub(crate) inning: Option<Inning>,
+    pub(crate) linescore: Linescore,
+    pub(crate) start_time: Option<DateTime<Local>>,
     pub(crate) description: String,
-    pub(crate) image: Vec<u8>,
-    pub(crate) image_w: u32,
-    pub(crate) image_h: u32
+    pub(crate) decisions: Decisions,
+    pub(crate) highlights: Vec<Highlight>,
+    pub(crate) image_cuts: Vec<statsapi::ImageCut>,
+    // Decoded thumbnails keyed by the src of their cut
+    pub(crate) images: HashMap<String, Vec<u8>>
 }
 
 impl GameModel {
-    pub fn from_json(data: &json::JsonValue) -> Self {
-        let (home_team, away_team) = GameModel::teams_from_json(data);
+    /// Builds the model without thumbnails, they are added as `spawn_fetch_thumbnails` delivers them
+    pub fn from_game(game: &statsapi::Game) -> Self {
+        let recap = game.recap();
 
         GameModel {
-            home_team,
-            away_team,
-            description: GameModel::desc_from_json(data),
-            image: GameModel::image_data_from_json(data),
-            image_w: data["content"]["editorial"]["recap"]["mlb"]["image"]["cuts"][18]["width"].as_u32().unwrap_or(215),
-            image_h: data["content"]["editorial"]["recap"]["mlb"]["image"]["cuts"][18]["height"].as_u32().unwrap_or(121)
+            game_pk: game.game_pk,
+            status: GameStatus::from_status(&game.status),
+            home_team: game.teams.home.team.name.clone(),
+            away_team: game.teams.away.team.name.clone(),
+            home_team_id: game.teams.home.team.id,
+            away_team_id: game.teams.away.team.id,
+            home_runs: game.teams.home.score,
+            away_runs: game.teams.away.score,
+            inning: game.linescore.as_ref().and_then(Inning::from_linescore),
+            linescore: game.linescore.as_ref().map(Linescore::from_linescore).unwrap_or_default(),
+            start_time: DateTime::parse_from_rfc3339(game.game_date.as_str()).ok().map(|start| start.with_timezone(&Local)),
+            description: recap.and_then(|recap| recap.headline.clone()).unwrap_or_default(),
+            decisions: game.decisions.as_ref().map(|decisions| Decisions::from_decisions(decisions, game.season())).unwrap_or_default(),
+            highlights: {
+                let mut highlights: Vec<Highlight> = game.highlights().into_iter().filter_map(Highlight::from_media).collect();
+                // The recap video is usually listed among the highlights again
+                let mut seen = HashSet::new();
+                highlights.retain(|highlight| seen.insert(highlight.url.clone()));
+                highlights
+            },
+            image_cuts: recap.and_then(|recap| recap.image.as_ref()).map(|image| image.cuts.clone()).unwrap_or_default(),
+            images: HashMap::new()
+        }
+    }
+
+    /// Takes over the parts of `update` that change while a game is played,
+    /// returns whether anything shown about the game changed
+    pub fn apply_update(&mut self, update: GameModel) -> bool {
+        let changed = self.status != update.status
+            || self.home_runs != update.home_runs
+            || self.away_runs != update.away_runs
+            || self.inning != update.inning
+            || self.linescore != update.linescore
+            || self.start_time != update.start_time
+            || self.description != update.description
+            || self.decisions != update.decisions
+            || self.highlights != update.highlights;
+
+        self.status = update.status;
+        self.home_runs = update.home_runs;
+        self.away_runs = update.away_runs;
+        self.inning = update.inning;
+        self.linescore = update.linescore;
+        self.start_time = update.start_time;
+        self.description = update.description;
+        self.decisions = update.decisions;
+        self.highlights = update.highlights;
+        changed
+    }
+
+    pub fn best_cut(&self, target: (u32, u32), pixel_ratio: f32) -> Option<&statsapi::ImageCut> {
+        select_cut(&self.image_cuts, target, pixel_ratio)
+    }
+
+    /// The thumbnail of a highlight, or no image until it has been fetched
+    pub fn highlight_thumbnail(&self, highlight: &Highlight, target: (u32, u32), pixel_ratio: f32) -> Vec<u8> {
+        highlight.best_cut(target, pixel_ratio)
+            .and_then(|cut| self.images.get(&cut.src))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// The thumbnail to show at `target`, falling back to the best of the other cuts that already arrived
+    /// and to no image at all while none has
+    pub fn thumbnail(&self, target: (u32, u32), pixel_ratio: f32) -> Vec<u8> {
+        let arrived = self.image_cuts.iter().filter(|cut| self.images.contains_key(&cut.src));
+        self.best_cut(target, pixel_ratio)
+            .and_then(|cut| self.images.get(&cut.src))
+            .or_else(|| select_cut(arrived, target, pixel_ratio).and_then(|cut| self.images.get(&cut.src)))
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// Picks the cut to display at `target`, a width and height in logical pixels, on a screen with `pixel_ratio` physical
+/// pixels per logical one. Cuts closest to the target's aspect ratio win, among those the smallest one covering the
+/// target in physical pixels, or the largest one if none is big enough.
+pub fn select_cut<'a>(cuts: impl IntoIterator<Item = &'a statsapi::ImageCut>, (target_w, target_h): (u32, u32), pixel_ratio: f32) -> Option<&'a statsapi::ImageCut> {
+    let wanted_w = (target_w as f32 * pixel_ratio).ceil() as u64;
+    let wanted_h = (target_h as f32 * pixel_ratio).ceil() as u64;
+    let target_aspect = target_w.max(1) as f32 / target_h.max(1) as f32;
+
+    cuts.into_iter()
+        .filter(|cut| cut.width > 0 && cut.height > 0)
+        .min_by_key(|cut| {
+            let aspect = cut.width as f32 / cut.height as f32;
+            // Buckets of roughly 2% so that rounding in the cut sizes does not decide the match
+            let aspect_distance = ((aspect / target_aspect).ln().abs() * 50.0).round() as u32;
+            let area = cut.width as u64 * cut.height as u64;
+            let covers = cut.width as u64 >= wanted_w && cut.height as u64 >= wanted_h;
+            if covers { (aspect_distance, 0, area) } else { (aspect_distance, 1, u64::MAX - area) }
+        })
+}
+
+pub struct BattingLine {
+    pub(crate) name: String,
+    pub(crate) position: String,
+    pub(crate) at_bats: u32,
+    pub(crate) runs: u32,
+    pub(crate) hits: u32,
+    pub(crate) rbi: u32,
+    pub(crate) walks: u32,
+    pub(crate) strikeouts: u32,
+    pub(crate) average: String
+}
+
+pub struct PitchingLine {
+    pub(crate) name: String,
+    pub(crate) innings_pitched: String,
+    pub(crate) hits: u32,
+    pub(crate) runs: u32,
+    pub(crate) earned_runs: u32,
+    pub(crate) walks: u32,
+    pub(crate) strikeouts: u32,
+    pub(crate) era: String
+}
+
+pub struct TeamBoxscore {
+    pub(crate) name: String,
+    pub(crate) batters: Vec<BattingLine>,
+    pub(crate) pitchers: Vec<PitchingLine>
+}
+
+impl TeamBoxscore {
+    fn from_team(team: &statsapi::BoxscoreTeam) -> Self {
+        let batters = team.batters.iter()
+            .filter_map(|id| team.player(*id))
+            .map(|player| {
+                let game = &player.stats.batting;
+                BattingLine {
+                    name: player.person.full_name.clone(),
+                    position: player.position.as_ref().map(|position| position.abbreviation.clone()).unwrap_or_default(),
+                    at_bats: game.at_bats.unwrap_or(0),
+                    runs: game.runs.unwrap_or(0),
+                    hits: game.hits.unwrap_or(0),
+                    rbi: game.rbi.unwrap_or(0),
+                    walks: game.base_on_balls.unwrap_or(0),
+                    strikeouts: game.strike_outs.unwrap_or(0),
+                    average: player.season_stats.batting.avg.clone().unwrap_or_default()
+                }
+            })
+            .collect();
+
+        let pitchers = team.pitchers.iter()
+            .filter_map(|id| team.player(*id))
+            .map(|player| {
+                let game = &player.stats.pitching;
+                PitchingLine {
+                    name: player.person.full_name.clone(),
+                    innings_pitched: game.innings_pitched.clone().unwrap_or_else(|| "0.0".to_owned()),
+                    hits: game.hits.unwrap_or(0),
+                    runs: game.runs.unwrap_or(0),
+                    earned_runs: game.earned_runs.unwrap_or(0),
+                    walks: game.base_on_balls.unwrap_or(0),
+                    strikeouts: game.strike_outs.unwrap_or(0),
+                    era: player.season_stats.pitching.era.clone().unwrap_or_default()
+                }
+            })
+            .collect();
+
+        TeamBoxscore { name: team.team.name.clone(), batters, pitchers }
+    }
+}
+
+pub struct Boxscore {
+    pub(crate) home: TeamBoxscore,
+    pub(crate) away: TeamBoxscore
+}
+
+impl Boxscore {
+    fn from_response(response: &statsapi::BoxscoreResponse) -> Self {
+        Boxscore {
+            home: TeamBoxscore::from_team(&response.teams.home),
+            away: TeamBoxscore::from_team(&response.teams.away)
+        }
+    }
+}
+
+pub struct Play {
+    pub(crate) inning: u32,
+    pub(crate) half: InningHalf,
+    pub(crate) batter: String,
+    pub(crate) pitcher: String,
+    pub(crate) description: String,
+    // Away and home runs after the play, only kept for plays that scored
+    pub(crate) score: Option<(u32, u32)>
+}
+
+impl Play {
+    /// Skips the at bat still in progress, which has no result yet
+    fn from_play(play: &statsapi::Play) -> Option<Self> {
+        let description = play.result.description.clone()?;
+        let score = match (play.about.is_scoring_play, play.result.away_score, play.result.home_score) {
+            (true, Some(away), Some(home)) => { Some((away, home)) }
+            _ => { None }
+        };
+        Some(Play {
+            inning: play.about.inning,
+            half: if play.about.half_inning == "bottom" { InningHalf::Bottom } else { InningHalf::Top },
+            batter: play.matchup.batter.full_name.clone(),
+            pitcher: play.matchup.pitcher.full_name.clone(),
+            description,
+            score
+        })
+    }
+
+    /// The half inning the play happened in, as in "Top 5th"
+    pub fn inning_label(&self) -> String {
+        let half = if self.half == InningHalf::Bottom { "Bot" } else { "Top" };
+        format!("{} {}", half, ordinal(self.inning))
+    }
+}
+
+fn ordinal(number: u32) -> String {
+    let suffix = match (number % 10, number % 100) {
+        (_, 11..=13) => { "th" }
+        (1, _) => { "st" }
+        (2, _) => { "nd" }
+        (3, _) => { "rd" }
+        _ => { "th" }
+    };
+    format!("{}{}", number, suffix)
+}
+
+pub struct TeamStanding {
+    pub(crate) name: String,
+    pub(crate) wins: u32,
+    pub(crate) losses: u32,
+    pub(crate) percentage: String,
+    pub(crate) games_back: String,
+    pub(crate) streak: String,
+    pub(crate) last_ten: Option<(u32, u32)>
+}
+
+pub struct DivisionStandings {
+    pub(crate) name: String,
+    pub(crate) teams: Vec<TeamStanding>
+}
+
+impl DivisionStandings {
+    fn from_record(record: &statsapi::StandingsRecord) -> Self {
+        DivisionStandings {
+            name: record.division.as_ref().and_then(|division| division.name.clone()).unwrap_or_default(),
+            teams: record.team_records.iter()
+                .map(|team| TeamStanding {
+                    name: team.team.name.clone(),
+                    wins: team.wins,
+                    losses: team.losses,
+                    percentage: team.winning_percentage.clone(),
+                    games_back: team.games_back.clone(),
+                    streak: team.streak.as_ref().map(|streak| streak.streak_code.clone()).unwrap_or_default(),
+                    last_ten: team.last_ten().map(|record| (record.wins, record.losses))
+                })
+                .collect()
         }
     }
+}
 
-    fn teams_from_json(data: &json::JsonValue) -> (String, String) {
-        ( data["teams"]["home"]["team"]["name"].as_str().unwrap().to_owned(), data["teams"]["away"]["team"]["name"].as_str().unwrap().to_owned() )
+pub struct Thumbnail {
+    pub(crate) game_pk: u64,
+    pub(crate) url: String,
+    pub(crate) image: Vec<u8>
+}
+
+const THUMBNAIL_WORKERS: usize = 4;
+
+/// Downloads the cuts of the thumbnails of `games` best matching each of `targets` on a small pool of worker threads.
+/// Each one is sent as soon as it is decoded, thumbnails that fail to load are skipped.
+pub fn spawn_fetch_thumbnails(fetcher: &Arc<Fetcher>, games: &[GameModel], targets: &[(u32, u32)], pixel_ratio: f32) -> Receiver<Thumbnail> {
+    let mut jobs = VecDeque::new();
+    let mut queued = HashSet::new();
+    // Targets go first to last so every game gets its first thumbnail before any gets its second
+    for target in targets {
+        for game in games {
+            if let Some(cut) = game.best_cut(*target, pixel_ratio) {
+                if queued.insert((game.game_pk, cut.src.clone())) {
+                    jobs.push_back((game.game_pk, cut.src.clone()));
+                }
+            }
+        }
     }
+    spawn_image_workers(fetcher, jobs)
+}
 
-    fn desc_from_json(data: &json::JsonValue) -> String {
-        if let Some(desc) = data["content"]["editorial"]["recap"]["mlb"]["headline"].as_str() {
-            desc.to_owned()
-        } else { String::new() }
+/// Downloads the thumbnails of the highlights of `game` for display at `target`
+pub fn spawn_fetch_highlight_thumbnails(fetcher: &Arc<Fetcher>, game: &GameModel, target: (u32, u32), pixel_ratio: f32) -> Receiver<Thumbnail> {
+    let mut jobs = VecDeque::new();
+    let mut queued = HashSet::new();
+    for highlight in &game.highlights {
+        if let Some(cut) = highlight.best_cut(target, pixel_ratio) {
+            if !game.images.contains_key(&cut.src) && queued.insert(cut.src.clone()) {
+                jobs.push_back((game.game_pk, cut.src.clone()));
+            }
+        }
     }
+    spawn_image_workers(fetcher, jobs)
+}
+
+fn spawn_image_workers(fetcher: &Arc<Fetcher>, jobs: VecDeque<(u64, String)>) -> Receiver<Thumbnail> {
+    let (sender, receiver) = mpsc::channel();
+    let worker_count = THUMBNAIL_WORKERS.min(jobs.len());
+    let jobs = Arc::new(Mutex::new(jobs));
 
-    fn image_data_from_json(data: &json::JsonValue) -> Vec<u8> {
-        let mut result = Vec::new();
-        match data["content"]["editorial"]["recap"]["mlb"]["image"]["cuts"][16]["src"].as_str() {
-            None => { result }
-            Some(url) => {
-                let jpg_bytes = fetch_bytes(url.to_owned());
-                match image::load_from_memory_with_format(jpg_bytes.as_bytes(), image::ImageFormat::Jpeg) {
-                    Ok(loaded_image) => {
-                        loaded_image.write_to(&mut result, image::ImageFormat::Png).unwrap();
-                        result
+    for _ in 0..worker_count {
+        let jobs = Arc::clone(&jobs);
+        let fetcher = Arc::clone(fetcher);
+        let sender = sender.clone();
+        thread::spawn(move || {
+            loop {
+                let job = jobs.lock().unwrap().pop_front();
+                let (game_pk, url) = match job {
+                    Some(job) => { job }
+                    None => { break }
+                };
+                if let Ok(image) = fetch_thumbnail(&fetcher, url.clone()) {
+                    if sender.send(Thumbnail { game_pk, url, image }).is_err() {
+                        break;
                     }
-                    Err(_) => { result }
                 }
             }
+        });
+    }
+
+    receiver
+}
+
+fn fetch_thumbnail(fetcher: &Fetcher, url: String) -> Result<Vec<u8>, Error> {
+    // The image behind a url never changes
+    let jpg_bytes = fetch_bytes(fetcher, url, Freshness::Forever)?;
+    let loaded_image = image::load_from_memory_with_format(jpg_bytes.as_bytes(), image::ImageFormat::Jpeg)
+        .map_err(|err| Error::Decode(err.to_string()))?;
+    let mut result = Vec::new();
+    loaded_image.write_to(&mut result, image::ImageFormat::Png)
+        .map_err(|err| Error::Decode(err.to_string()))?;
+    Ok(result)
+}
+
+// Today's schedule changes as games progress, a day whose games are all final does not
+const SCHEDULE_FRESHNESS: Freshness = Freshness::For(Duration::from_secs(60));
+
+/// Where the data comes from: the api `query` builds the urls for, through `cache` unless `fixtures` are recorded or replayed.
+/// Shared with the worker threads fetching in the background.
+pub struct Fetcher {
+    pub(crate) query: ScheduleQuery,
+    cache: Option<HttpCache>,
+    fixtures: Option<Fixtures>
+}
+
+impl Fetcher {
+    pub fn new(query: ScheduleQuery, cache: Option<HttpCache>, fixtures: Option<Fixtures>) -> Self {
+        Fetcher { query, cache, fixtures }
+    }
+}
+
+/// Teams given by their statsapi id, like 147, or by name, like "Yankees" or "New York Yankees",
+/// such as the favorites or the ones the schedule is narrowed down to
+#[derive(Debug, Default)]
+pub struct Teams {
+    ids: HashSet<u64>,
+    names: Vec<String>
+}
+
+impl Teams {
+    pub fn new(teams: &[&str]) -> Self {
+        let mut parsed = Teams::default();
+        for team in teams.iter().map(|team| team.trim()).filter(|team| !team.is_empty()) {
+            match team.parse() {
+                Ok(id) => { parsed.ids.insert(id); }
+                Err(_) => { parsed.names.push(team.to_lowercase()) }
+            }
         }
+        parsed
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty() && self.names.is_empty()
+    }
+
+    /// Whether one of these teams plays in `game`
+    pub fn plays_in(&self, game: &GameModel) -> bool {
+        self.contains(game.home_team_id, &game.home_team) || self.contains(game.away_team_id, &game.away_team)
+    }
+
+    /// Names match the full team name or its last words, so "Sox" matches both Red Sox and White Sox
+    fn contains(&self, id: u64, name: &str) -> bool {
+        let name = name.to_lowercase();
+        self.ids.contains(&id) || self.names.iter().any(|team| {
+            name == *team || name.ends_with(format!(" {}", team).as_str())
+        })
     }
 }
 
-pub fn fetch_bytes(url: String) -> Vec<u8> {
-    let mut bytes = Vec::new();
+struct HttpResponse {
+    code: u32,
+    body: Vec<u8>,
+    etag: Option<String>,
+    last_modified: Option<String>
+}
+
+fn perform_request(url: &str, headers: Vec<String>) -> Result<HttpResponse, Error> {
+    let mut body = Vec::new();
+    let mut etag = None;
+    let mut last_modified = None;
     let mut easy_handle = Easy::new();
-    easy_handle.url(url.as_str()).unwrap();
+    debug!("Requesting {}", url);
+    easy_handle.url(url)?;
+    easy_handle.follow_location(true)?;
+
+    if !headers.is_empty() {
+        let mut list = List::new();
+        for header in headers {
+            list.append(header.as_str())?;
+        }
+        easy_handle.http_headers(list)?;
+    }
 
     {
         let mut transfer = easy_handle.transfer();
         transfer.write_function(|data| {
-            bytes.extend_from_slice(data);
+            body.extend_from_slice(data);
             Ok(data.len())
-        }).unwrap();
-        transfer.perform().unwrap();
+        })?;
+        transfer.header_function(|line| {
+            if let Ok(line) = std::str::from_utf8(line) {
+                if let Some(split) = line.find(':') {
+                    let value = line[split + 1..].trim().to_owned();
+                    match line[..split].to_ascii_lowercase().as_str() {
+                        "etag" => { etag = Some(value) }
+                        "last-modified" => { last_modified = Some(value) }
+                        _ => {}
+                    }
+                }
+            }
+            true
+        })?;
+        transfer.perform()?;
     }
 
-    bytes
+    Ok(HttpResponse { code: easy_handle.response_code()?, body, etag, last_modified })
 }
 
-pub fn fetch_json(url: String) -> JsonValue {
-    let bytes = fetch_bytes(url);
-    let json_string = String::from_utf8(bytes).unwrap();
-    json::parse(json_string.as_str()).unwrap()
+// Redirects have been followed by then, so anything else is an answer without the body asked for
+fn is_success(code: u32) -> bool {
+    (200..300).contains(&code)
 }
 
-pub fn fetch_games(url: String) -> Vec<GameModel> {
-    let data = fetch_json(url);
-    let mut models= Vec::new();
+pub fn fetch_bytes(fetcher: &Fetcher, url: String, freshness: Freshness) -> Result<Vec<u8>, Error> {
+    match &fetcher.fixtures {
+        Some(fixtures) if fixtures.is_replay() => {
+            fixtures.load(url.as_str()).ok_or(Error::FixtureMissing(url))
+        }
+        Some(fixtures) => {
+            // Straight from the network, the cache could hand out an outdated copy
+            let bytes = fetch_from_network(url.clone())?;
+            if let Err(err) = fixtures.save(url.as_str(), &bytes) {
+                warn!("Could not record {}: {}", url, err);
+            }
+            Ok(bytes)
+        }
+        None => { fetch_through_cache(fetcher, url, freshness) }
+    }
+}
 
-    if let json::JsonValue::Array(games) = &data["dates"][0]["games"] {
-        for game in games {
-            if ! game.is_null() {
-                models.push(GameModel::from_json(game))
+fn fetch_from_network(url: String) -> Result<Vec<u8>, Error> {
+    let response = perform_request(url.as_str(), Vec::new())?;
+    if !is_success(response.code) {
+        return Err(Error::HttpStatus { url, code: response.code });
+    }
+    Ok(response.body)
+}
+
+/// Serves `url` from the cache while it is fresh, otherwise revalidates or downloads it.
+/// A stale copy is still better than nothing when the network is down.
+fn fetch_through_cache(fetcher: &Fetcher, url: String, freshness: Freshness) -> Result<Vec<u8>, Error> {
+    let cache = match &fetcher.cache {
+        Some(cache) => { cache }
+        None => { return fetch_from_network(url) }
+    };
+
+    let cached = cache.lookup(url.as_str());
+    let mut validators = Vec::new();
+    if let Some(cached) = &cached {
+        if cached.fresh && !matches!(freshness, Freshness::Revalidate) {
+            return Ok(cached.body.clone());
+        }
+        if let Some(etag) = &cached.etag {
+            validators.push(format!("If-None-Match: {}", etag));
+        }
+        if let Some(last_modified) = &cached.last_modified {
+            validators.push(format!("If-Modified-Since: {}", last_modified));
+        }
+    }
+
+    match (perform_request(url.as_str(), validators), cached) {
+        (Ok(response), Some(cached)) if response.code == 304 => {
+            cache.refresh(url.as_str(), freshness);
+            Ok(cached.body)
+        }
+        (Ok(response), _) if !is_success(response.code) => { Err(Error::HttpStatus { url, code: response.code }) }
+        (Ok(response), _) => {
+            cache.store(url.as_str(), &response.body, response.etag, response.last_modified, freshness);
+            Ok(response.body)
+        }
+        (Err(Error::Transport(_)), Some(cached)) => { Ok(cached.body) }
+        (Err(err), _) => { Err(err) }
+    }
+}
+
+pub fn fetch_json<T: DeserializeOwned>(fetcher: &Fetcher, url: String, freshness: Freshness) -> Result<T, Error> {
+    let bytes = fetch_bytes(fetcher, url, freshness)?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+pub fn fetch_games(fetcher: &Fetcher, url: String) -> Result<Vec<GameModel>, Error> {
+    fetch_games_with_freshness(fetcher, url, SCHEDULE_FRESHNESS)
+}
+
+fn fetch_games_with_freshness(fetcher: &Fetcher, url: String, freshness: Freshness) -> Result<Vec<GameModel>, Error> {
+    let schedule: statsapi::ScheduleResponse = fetch_json(fetcher, url.clone(), freshness)?;
+    let games = schedule.dates.first().map(|date| date.games.as_slice()).unwrap_or(&[]);
+
+    if !games.is_empty() && games.iter().all(|game| game.is_final()) {
+        if let Some(cache) = &fetcher.cache {
+            cache.refresh(url.as_str(), Freshness::Forever);
+        }
+    }
+
+    Ok(games.iter().map(GameModel::from_game).collect())
+}
+
+/// Runs `fetch` on a worker thread, the result arrives on the returned channel
+fn spawn_fetch<T: Send + 'static>(fetch: impl FnOnce() -> Result<T, Error> + Send + 'static) -> Receiver<Result<T, Error>> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        // The receiver is gone if the viewer moved on in the meantime
+        let _ = sender.send(fetch());
+    });
+    receiver
+}
+
+pub fn spawn_fetch_games(fetcher: &Arc<Fetcher>, url: String) -> Receiver<Result<Vec<GameModel>, Error>> {
+    let fetcher = Arc::clone(fetcher);
+    spawn_fetch(move || fetch_games(&fetcher, url))
+}
+
+pub fn fetch_boxscore(fetcher: &Fetcher, url: String) -> Result<Boxscore, Error> {
+    let response: statsapi::BoxscoreResponse = fetch_json(fetcher, url, SCHEDULE_FRESHNESS)?;
+    Ok(Boxscore::from_response(&response))
+}
+
+pub fn spawn_fetch_boxscore(fetcher: &Arc<Fetcher>, url: String) -> Receiver<Result<Boxscore, Error>> {
+    let fetcher = Arc::clone(fetcher);
+    spawn_fetch(move || fetch_boxscore(&fetcher, url))
+}
+
+// The leagues of a sport hardly ever change within a season
+const LEAGUES_FRESHNESS: Freshness = Freshness::For(Duration::from_secs(24 * 60 * 60));
+
+/// Every division of the leagues of the queried sport in the season of `date`, in the order the api lists them
+pub fn fetch_standings(fetcher: &Fetcher, date: NaiveDate) -> Result<Vec<DivisionStandings>, Error> {
+    let leagues: statsapi::LeaguesResponse = fetch_json(fetcher, fetcher.query.url_for_leagues(date), LEAGUES_FRESHNESS)?;
+    let league_ids: Vec<u64> = leagues.leagues.iter().map(|league| league.id).collect();
+    if league_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let response: statsapi::StandingsResponse = fetch_json(fetcher, fetcher.query.url_for_standings(date, &league_ids), SCHEDULE_FRESHNESS)?;
+    Ok(response.records.iter().map(DivisionStandings::from_record).collect())
+}
+
+pub fn spawn_fetch_standings(fetcher: &Arc<Fetcher>, date: NaiveDate) -> Receiver<Result<Vec<DivisionStandings>, Error>> {
+    let fetcher = Arc::clone(fetcher);
+    spawn_fetch(move || fetch_standings(&fetcher, date))
+}
+
+pub fn fetch_plays(fetcher: &Fetcher, url: String) -> Result<Vec<Play>, Error> {
+    let feed: statsapi::LiveFeedResponse = fetch_json(fetcher, url, SCHEDULE_FRESHNESS)?;
+    Ok(feed.live_data.plays.all_plays.iter().filter_map(Play::from_play).collect())
+}
+
+pub fn spawn_fetch_plays(fetcher: &Arc<Fetcher>, url: String) -> Receiver<Result<Vec<Play>, Error>> {
+    let fetcher = Arc::clone(fetcher);
+    spawn_fetch(move || fetch_plays(&fetcher, url))
+}
+
+// Games that will not start for a while are polled at most this often
+pub const DEFAULT_LIVE_POLL_INTERVAL: Duration = Duration::from_secs(15);
+const MAX_POLL_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// How long to wait before polling `games` again, `None` once none of them can change anymore.
+/// Games in progress are polled every `live_interval`, games yet to start less often the farther away they are.
+pub fn poll_interval(games: &[GameModel], live_interval: Duration) -> Option<Duration> {
+    let now = Local::now();
+    let mut interval: Option<Duration> = None;
+
+    for game in games {
+        let game_interval = match game.status {
+            GameStatus::InProgress | GameStatus::Delayed | GameStatus::PreGame => { live_interval }
+            GameStatus::Scheduled => {
+                let until_start = game.start_time
+                    .and_then(|start| (start - now).to_std().ok())
+                    .unwrap_or(live_interval);
+                until_start.max(live_interval).min(MAX_POLL_INTERVAL)
+            }
+            GameStatus::Final | GameStatus::Postponed => { continue }
+        };
+        interval = Some(interval.map_or(game_interval, |interval| interval.min(game_interval)));
+    }
+
+    interval
+}
+
+/// The games of one day as polled in the background, dropping it stops the polling
+pub struct GamePoller {
+    pub(crate) updates: Receiver<Vec<GameModel>>,
+    // Never sent on, the polling thread stops as soon as it is dropped
+    _stop: Sender<()>
+}
+
+/// Re-fetches the schedule in the background for as long as `poll_interval` asks for it and the poller is kept.
+/// Every successful poll is sent to the poller, failed ones are retried at the next interval.
+pub fn spawn_poll_games(fetcher: &Arc<Fetcher>, url: String, games: &[GameModel], live_interval: Duration) -> GamePoller {
+    let (sender, receiver) = mpsc::channel();
+    let (stop_sender, stop) = mpsc::channel::<()>();
+    let mut interval = poll_interval(games, live_interval);
+    let fetcher = Arc::clone(fetcher);
+
+    thread::spawn(move || {
+        while let Some(wait) = interval {
+            // Waits for the next poll unless the viewer leaves this day in the meantime
+            if !matches!(stop.recv_timeout(wait), Err(RecvTimeoutError::Timeout)) {
+                break;
+            }
+            let polled = fetch_games_with_freshness(&fetcher, url.clone(), Freshness::Revalidate);
+            if matches!(stop.try_recv(), Err(TryRecvError::Disconnected)) {
+                break;
+            }
+            match polled {
+                Ok(games) => {
+                    interval = poll_interval(&games, live_interval);
+                    if sender.send(games).is_err() {
+                        break;
+                    }
+                }
+                Err(err) => { warn!("Polling {} failed: {}", url, err) }
+            }
+        }
+    });
+
+    GamePoller { updates: receiver, _stop: stop_sender }
+}
+
+#[derive(Debug)]
+pub enum QueryError {
+    InvalidDate(String),
+    DateOutOfRange(NaiveDate),
+    InvalidBaseUrl(String),
+    InvalidHydrate(String)
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            QueryError::InvalidDate(input) => { write!(f, "'{}' is not a date in YYYY-MM-DD format", input) }
+            QueryError::DateOutOfRange(date) => { write!(f, "{} is before the first season ({})", date, FIRST_SEASON) }
+            QueryError::InvalidBaseUrl(url) => { write!(f, "'{}' is not an http(s) url", url) }
+            QueryError::InvalidHydrate(entry) => { write!(f, "'{}' is not a valid hydrate entry", entry) }
+        }
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+/// Whether every opening parenthesis of a hydrate entry is closed again
+fn balanced(entry: &str) -> bool {
+    let mut depth = 0i32;
+    for c in entry.chars() {
+        match c {
+            '(' => { depth += 1 }
+            ')' => { depth -= 1 }
+            _ => {}
+        }
+        if depth < 0 {
+            return false;
+        }
+    }
+    depth == 0
+}
+
+pub struct ScheduleQuery {
+    base_url: String,
+    hydrate: Vec<String>,
+    sport_id: u32
+}
+
+impl ScheduleQuery {
+    pub fn new(base_url: &str, hydrate: &[&str], sport_id: u32) -> Result<Self, QueryError> {
+        let base_url = base_url.trim_end_matches('/');
+        if !(base_url.starts_with("http://") || base_url.starts_with("https://")) || base_url.contains(char::is_whitespace) {
+            return Err(QueryError::InvalidBaseUrl(base_url.to_owned()));
+        }
+
+        for entry in hydrate {
+            if entry.is_empty() || entry.contains(|c: char| c == '&' || c == '#' || c.is_whitespace()) || !balanced(entry) {
+                return Err(QueryError::InvalidHydrate((*entry).to_owned()));
             }
         }
+
+        Ok(ScheduleQuery {
+            base_url: base_url.to_owned(),
+            hydrate: hydrate.iter().map(|entry| (*entry).to_owned()).collect(),
+            sport_id
+        })
+    }
+
+    pub fn url_for_date(&self, date: NaiveDate) -> String {
+        let mut url = format!("{}/schedule?", self.base_url);
+        if !self.hydrate.is_empty() {
+            url.push_str(format!("hydrate={}&", self.hydrate.join(",")).as_str());
+        }
+        url.push_str(format!("date={}&sportId={}", date.format("%Y-%m-%d"), self.sport_id).as_str());
+        url
+    }
+
+    pub fn url_for_boxscore(&self, game_pk: u64) -> String {
+        format!("{}/game/{}/boxscore", self.base_url, game_pk)
     }
 
-    models
+    /// The leagues of the queried sport, such as the American and National League for MLB
+    pub fn url_for_leagues(&self, date: NaiveDate) -> String {
+        format!("{}/league?sportId={}&season={}", self.base_url, self.sport_id, date.year())
+    }
+
+    pub fn url_for_standings(&self, date: NaiveDate, league_ids: &[u64]) -> String {
+        let league_ids: Vec<String> = league_ids.iter().map(|id| id.to_string()).collect();
+        format!("{}/standings?leagueId={}&season={}&date={}&standingsTypes=regularSeason&hydrate=division",
+            self.base_url, league_ids.join(","), date.year(), date.format("%Y-%m-%d"))
+    }
+
+    /// The live feed is only served by version 1.1 of the api
+    pub fn url_for_live_feed(&self, game_pk: u64) -> String {
+        let base_url = match self.base_url.strip_suffix("/v1") {
+            Some(api_url) => { format!("{}/v1.1", api_url) }
+            None => { self.base_url.clone() }
+        };
+        format!("{}/game/{}/feed/live", base_url, game_pk)
+    }
+}
+
+impl Default for ScheduleQuery {
+    fn default() -> Self {
+        ScheduleQuery::new(DEFAULT_BASE_URL, &DEFAULT_HYDRATE, DEFAULT_SPORT_ID).unwrap()
+    }
 }
 
-pub fn make_url_for_date(_: String) -> String {
-    // Todo -- Construct Url from date string
-    "http://statsapi.mlb.com/api/v1/schedule?hydrate=game(content(editorial(recap))),decisions&date=2020-09-01&sportId=1".to_owned()
-}
\ No newline at end of file
+/// The current date at the league office, which is what the statsapi uses to group games into days
+pub fn league_today() -> NaiveDate {
+    Utc::now().with_timezone(&New_York).naive_local().date()
+}
+
+/// Parses a YYYY-MM-DD date, an empty input is today
+pub fn parse_date(input: &str) -> Result<NaiveDate, QueryError> {
+    if input.trim().is_empty() {
+        return Ok(league_today());
+    }
+    let date = NaiveDate::parse_from_str(input.trim(), "%Y-%m-%d")
+        .map_err(|_| QueryError::InvalidDate(input.to_owned()))?;
+    check_season(date)
+}
+
+fn check_season(date: NaiveDate) -> Result<NaiveDate, QueryError> {
+    if date.year() < FIRST_SEASON {
+        return Err(QueryError::DateOutOfRange(date));
+    }
+    Ok(date)
+}
+
+/// Builds the schedule url for `date`, which may have been stepped to from a valid one past the first season
+pub fn make_url_for_date(query: &ScheduleQuery, date: NaiveDate) -> Result<String, QueryError> {
+    Ok(query.url_for_date(check_season(date)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn cut(width: u32, height: u32) -> statsapi::ImageCut {
+        statsapi::ImageCut { width, height, src: format!("https://img.mlbstatic.com/{}x{}.jpg", width, height) }
+    }
+
+    fn game_model(game_pk: u64) -> GameModel {
+        GameModel {
+            game_pk,
+            status: GameStatus::Scheduled,
+            home_team: "New York Yankees".to_owned(),
+            away_team: "Boston Red Sox".to_owned(),
+            home_team_id: 147,
+            away_team_id: 111,
+            home_runs: None,
+            away_runs: None,
+            inning: None,
+            linescore: Linescore::default(),
+            start_time: None,
+            description: String::new(),
+            decisions: Decisions::default(),
+            highlights: Vec::new(),
+            image_cuts: Vec::new(),
+            images: HashMap::new()
+        }
+    }
+
+    #[test]
+    fn parses_dates() {
+        assert_eq!(parse_date("2020-09-01").unwrap(), date(2020, 9, 1));
+        assert_eq!(parse_date(" 2020-09-01\n").unwrap(), date(2020, 9, 1));
+    }
+
+    #[test]
+    fn parses_an_empty_date_as_today() {
+        assert_eq!(parse_date("").unwrap(), league_today());
+        assert_eq!(parse_date("  ").unwrap(), league_today());
+    }
+
+    #[test]
+    fn rejects_invalid_dates() {
+        for input in ["2020-13-01", "2020-02-30", "09/01/2020", "2020-9", "today"].iter() {
+            assert!(matches!(parse_date(input), Err(QueryError::InvalidDate(_))), "{} was accepted", input);
+        }
+    }
+
+    #[test]
+    fn rejects_dates_before_the_first_season() {
+        assert!(matches!(parse_date("1875-12-31"), Err(QueryError::DateOutOfRange(_))));
+        assert_eq!(parse_date("1876-01-01").unwrap(), date(1876, 1, 1));
+    }
+
+    #[test]
+    fn only_builds_urls_from_the_first_season_on() {
+        let query = ScheduleQuery::default();
+        assert!(matches!(make_url_for_date(&query, date(1875, 12, 31)), Err(QueryError::DateOutOfRange(_))));
+        assert!(make_url_for_date(&query, date(1876, 1, 1)).unwrap().contains("date=1876-01-01"));
+    }
+
+    #[test]
+    fn rejects_invalid_base_urls() {
+        for base_url in ["statsapi.mlb.com/api/v1", "ftp://statsapi.mlb.com", "http://stats api.mlb.com"].iter() {
+            assert!(matches!(ScheduleQuery::new(base_url, &[], 1), Err(QueryError::InvalidBaseUrl(_))), "{} was accepted", base_url);
+        }
+    }
+
+    #[test]
+    fn rejects_hydrate_entries_breaking_the_query() {
+        for entry in ["", "decisions&sportId=2", "linescore#", "game content", "game(content", "decisions)("].iter() {
+            assert!(matches!(ScheduleQuery::new(DEFAULT_BASE_URL, &[entry], 1), Err(QueryError::InvalidHydrate(_))), "'{}' was accepted", entry);
+        }
+    }
+
+    #[test]
+    fn builds_schedule_urls() {
+        let query = ScheduleQuery::new("https://example.com/api/v1/", &["game(content(editorial(recap)))", "decisions"], 11).unwrap();
+        assert_eq!(query.url_for_date(date(2020, 9, 1)),
+            "https://example.com/api/v1/schedule?hydrate=game(content(editorial(recap))),decisions&date=2020-09-01&sportId=11");
+
+        let query = ScheduleQuery::new(DEFAULT_BASE_URL, &[], DEFAULT_SPORT_ID).unwrap();
+        assert_eq!(query.url_for_date(date(2021, 4, 1)), "http://statsapi.mlb.com/api/v1/schedule?date=2021-04-01&sportId=1");
+    }
+
+    #[test]
+    fn builds_detail_urls() {
+        let query = ScheduleQuery::default();
+        assert_eq!(query.url_for_boxscore(631377), "http://statsapi.mlb.com/api/v1/game/631377/boxscore");
+        assert_eq!(query.url_for_live_feed(631377), "http://statsapi.mlb.com/api/v1.1/game/631377/feed/live");
+    }
+
+    #[test]
+    fn builds_standings_urls_for_the_queried_sport() {
+        let query = ScheduleQuery::new(DEFAULT_BASE_URL, &[], 11).unwrap();
+        assert_eq!(query.url_for_leagues(date(2021, 6, 1)), "http://statsapi.mlb.com/api/v1/league?sportId=11&season=2021");
+        assert_eq!(query.url_for_standings(date(2021, 6, 1), &[117, 118]),
+            "http://statsapi.mlb.com/api/v1/standings?leagueId=117,118&season=2021&date=2021-06-01&standingsTypes=regularSeason&hydrate=division");
+    }
+
+    #[test]
+    fn accepts_the_default_hydrate() {
+        assert!(ScheduleQuery::new(DEFAULT_BASE_URL, &DEFAULT_HYDRATE, DEFAULT_SPORT_ID).is_ok());
+    }
+
+    #[test]
+    fn reads_the_season_record_of_the_decisions() {
+        let schedule: statsapi::ScheduleResponse = serde_json::from_str(include_str!("../testdata/schedule_final_with_decisions.json")).unwrap();
+        let game = GameModel::from_game(&schedule.dates[0].games[0]);
+        let winner = game.decisions.winner.unwrap();
+        assert_eq!(winner.name, "Martin Perez");
+        assert_eq!(winner.record().as_deref(), Some("3-3, 3.45 ERA"));
+        // Stats of another season are not this game's record
+        assert_eq!(game.decisions.loser.unwrap().record(), None);
+        assert_eq!(game.decisions.save.unwrap().saves, Some(4));
+    }
+
+    #[test]
+    fn matches_teams_by_id_and_name() {
+        let teams = Teams::new(&["147", "Cubs", "boston red sox"]);
+        assert!(teams.contains(147, "New York Yankees"));
+        assert!(teams.contains(112, "Chicago Cubs"));
+        assert!(teams.contains(111, "Boston Red Sox"));
+        assert!(!teams.contains(145, "Chicago White Sox"));
+        // Only whole words at the end of the name
+        assert!(!Teams::new(&["ubs"]).contains(112, "Chicago Cubs"));
+    }
+
+    #[test]
+    fn matches_the_last_words_of_every_team_sharing_them() {
+        let teams = Teams::new(&["SOX"]);
+        assert!(teams.contains(111, "Boston Red Sox"));
+        assert!(teams.contains(145, "Chicago White Sox"));
+        assert!(Teams::new(&["White Sox"]).contains(145, "Chicago White Sox"));
+        assert!(!Teams::new(&["White Sox"]).contains(111, "Boston Red Sox"));
+    }
+
+    #[test]
+    fn skips_empty_teams() {
+        let teams = Teams::new(&["", "  ", ""]);
+        assert!(teams.is_empty());
+        assert!(!teams.contains(0, ""));
+        assert!(!Teams::new(&[" 147 ", ""]).is_empty());
+    }
+
+    #[test]
+    fn matches_games_of_either_team() {
+        let game = game_model(1);
+        assert!(Teams::new(&["Yankees"]).plays_in(&game));
+        assert!(Teams::new(&["111"]).plays_in(&game));
+        assert!(!Teams::new(&["Mets"]).plays_in(&game));
+    }
+
+    #[test]
+    fn reports_teams_without_an_id_as_drift() {
+        let err = serde_json::from_str::<statsapi::Team>("{\"name\": \"New York Yankees\"}").unwrap_err();
+        assert!(matches!(Error::from(err), Error::Schema(_)));
+    }
+
+    #[test]
+    fn selects_the_smallest_cut_covering_the_target() {
+        let cuts = vec![cut(1280, 720), cut(320, 180), cut(640, 360)];
+        assert_eq!(select_cut(&cuts, (200, 112), 1.0), Some(&cuts[1]));
+        assert_eq!(select_cut(&cuts, (320, 180), 1.0), Some(&cuts[1]));
+        // Twice the pixels on a high density display
+        assert_eq!(select_cut(&cuts, (200, 112), 2.0), Some(&cuts[2]));
+    }
+
+    #[test]
+    fn selects_the_largest_cut_when_none_covers_the_target() {
+        let cuts = vec![cut(320, 180), cut(1280, 720), cut(640, 360)];
+        assert_eq!(select_cut(&cuts, (1920, 1080), 1.0), Some(&cuts[1]));
+    }
+
+    #[test]
+    fn prefers_the_aspect_ratio_over_the_size() {
+        let cuts = vec![cut(320, 180), cut(1280, 720), cut(800, 600)];
+        assert_eq!(select_cut(&cuts, (120, 90), 1.0), Some(&cuts[2]));
+        assert_eq!(select_cut(&cuts, (160, 90), 1.0), Some(&cuts[0]));
+    }
+
+    #[test]
+    fn skips_empty_cuts() {
+        let cuts = vec![cut(0, 0), cut(640, 0)];
+        assert_eq!(select_cut(&cuts, (200, 112), 1.0), None);
+        assert_eq!(select_cut(&[], (200, 112), 1.0), None);
+    }
+
+    #[test]
+    fn falls_back_to_the_best_cut_that_arrived() {
+        let mut game = game_model(1);
+        game.image_cuts = vec![cut(320, 180), cut(640, 360), cut(1280, 720), cut(2560, 1440)];
+        for (index, image) in [(0, b"small"), (2, b"large"), (3, b"giant")].iter() {
+            game.images.insert(game.image_cuts[*index].src.clone(), image.to_vec());
+        }
+
+        // The 640x360 cut has not arrived, the 1280x720 one is the next best
+        assert_eq!(game.thumbnail((600, 338), 1.0), b"large");
+        game.images.insert(game.image_cuts[1].src.clone(), b"medium".to_vec());
+        assert_eq!(game.thumbnail((600, 338), 1.0), b"medium");
+        assert!(game_model(2).thumbnail((600, 338), 1.0).is_empty());
+    }
+
+    fn game_at(status: GameStatus, start_in: Option<chrono::Duration>) -> GameModel {
+        let mut game = game_model(1);
+        game.status = status;
+        game.start_time = start_in.map(|start_in| Local::now() + start_in);
+        game
+    }
+
+    const LIVE: Duration = Duration::from_secs(15);
+
+    #[test]
+    fn stops_polling_once_every_game_is_over() {
+        let games = [game_at(GameStatus::Final, None), game_at(GameStatus::Postponed, None)];
+        assert_eq!(poll_interval(&games, LIVE), None);
+        assert_eq!(poll_interval(&[], LIVE), None);
+    }
+
+    #[test]
+    fn polls_games_in_progress_at_the_live_interval() {
+        for status in [GameStatus::InProgress, GameStatus::Delayed, GameStatus::PreGame].iter() {
+            let games = [game_at(GameStatus::Final, None), game_at(*status, None)];
+            assert_eq!(poll_interval(&games, LIVE), Some(LIVE));
+        }
+    }
+
+    #[test]
+    fn polls_scheduled_games_around_their_start() {
+        let soon = poll_interval(&[game_at(GameStatus::Scheduled, Some(chrono::Duration::minutes(2)))], LIVE).unwrap();
+        assert!(soon > Duration::from_secs(110) && soon <= Duration::from_secs(120), "{:?}", soon);
+
+        let later = [game_at(GameStatus::Scheduled, Some(chrono::Duration::hours(3)))];
+        assert_eq!(poll_interval(&later, LIVE), Some(MAX_POLL_INTERVAL));
+
+        // Late or without a start time, the game may begin at any moment
+        let late = [game_at(GameStatus::Scheduled, Some(chrono::Duration::minutes(-5)))];
+        assert_eq!(poll_interval(&late, LIVE), Some(LIVE));
+        assert_eq!(poll_interval(&[game_at(GameStatus::Scheduled, None)], LIVE), Some(LIVE));
+    }
+
+    #[test]
+    fn polls_as_often_as_the_most_urgent_game_needs() {
+        let games = [
+            game_at(GameStatus::Scheduled, Some(chrono::Duration::hours(3))),
+            game_at(GameStatus::Scheduled, Some(chrono::Duration::minutes(1))),
+            game_at(GameStatus::Final, None)
+        ];
+        let interval = poll_interval(&games, LIVE).unwrap();
+        assert!(interval > LIVE && interval <= Duration::from_secs(60), "{:?}", interval);
+    }
+
+    #[test]
+    fn fetches_through_the_fixtures_it_is_given() {
+        use crate::fixtures::FixtureMode;
+
+        let dir = std::env::temp_dir().join(format!("mlb_game_viewer_data_{}", std::process::id()));
+        let query = ScheduleQuery::new("https://example.com/api/v1", &[], 11).unwrap();
+        let url = make_url_for_date(&query, date(2020, 9, 1)).unwrap();
+        Fixtures::new(dir.clone(), FixtureMode::Record).unwrap()
+            .save(url.as_str(), include_bytes!("../testdata/schedule_final_with_decisions.json")).unwrap();
+
+        let fetcher = Fetcher::new(query, None, Some(Fixtures::new(dir.clone(), FixtureMode::Replay).unwrap()));
+        assert_eq!(fetch_games(&fetcher, url).unwrap().len(), 1);
+        let other = make_url_for_date(&fetcher.query, date(2020, 9, 2)).unwrap();
+        assert!(matches!(fetch_games(&fetcher, other), Err(Error::FixtureMissing(_))));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
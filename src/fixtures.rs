@@ -0,0 +1,120 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::util::{fnv1a, write_atomically};
+
+pub enum FixtureMode {
+    /// Every response fetched from the network is written to the fixture directory
+    Record,
+    /// Responses are only ever served from the fixture directory
+    Replay
+}
+
+/// A directory of recorded url to response body pairs, stored as `<hash>.url` and `<hash>.body`
+pub struct Fixtures {
+    dir: PathBuf,
+    mode: FixtureMode
+}
+
+impl Fixtures {
+    pub fn new(dir: PathBuf, mode: FixtureMode) -> io::Result<Self> {
+        match mode {
+            FixtureMode::Record => { fs::create_dir_all(&dir)? }
+            FixtureMode::Replay => {
+                if !dir.is_dir() {
+                    return Err(io::Error::new(io::ErrorKind::NotFound, format!("fixture directory {} does not exist", dir.display())));
+                }
+            }
+        }
+        Ok(Fixtures { dir, mode })
+    }
+
+    pub fn is_replay(&self) -> bool {
+        matches!(self.mode, FixtureMode::Replay)
+    }
+
+    pub fn load(&self, url: &str) -> Option<Vec<u8>> {
+        let (url_path, body_path) = self.paths(url);
+        if fs::read_to_string(url_path).ok()?.trim_end() != url {
+            return None;
+        }
+        fs::read(body_path).ok()
+    }
+
+    /// Writes the url last, so that an interrupted recording is never replayed
+    pub fn save(&self, url: &str, body: &[u8]) -> io::Result<()> {
+        let (url_path, body_path) = self.paths(url);
+        write_atomically(&body_path, body)?;
+        write_atomically(&url_path, format!("{}\n", url).as_bytes())
+    }
+
+    fn paths(&self, url: &str) -> (PathBuf, PathBuf) {
+        let name = format!("{:016x}", fnv1a(url.as_bytes()));
+        (self.dir.join(format!("{}.url", name)), self.dir.join(format!("{}.body", name)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A fresh directory per test, removed again when the test is done
+    struct TestDir(PathBuf);
+
+    impl TestDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("mlb_game_viewer_fixtures_{}_{}", std::process::id(), name));
+            let _ = fs::remove_dir_all(&dir);
+            TestDir(dir)
+        }
+    }
+
+    impl Drop for TestDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    const URL: &str = "http://statsapi.mlb.com/api/v1/schedule?date=2020-09-01&sportId=1";
+
+    #[test]
+    fn replays_what_was_recorded() {
+        let dir = TestDir::new("round_trip");
+        let recorder = Fixtures::new(dir.0.clone(), FixtureMode::Record).unwrap();
+        assert!(!recorder.is_replay());
+        recorder.save(URL, b"{\"dates\":[]}").unwrap();
+
+        let replayer = Fixtures::new(dir.0.clone(), FixtureMode::Replay).unwrap();
+        assert!(replayer.is_replay());
+        assert_eq!(replayer.load(URL).unwrap(), b"{\"dates\":[]}");
+    }
+
+    #[test]
+    fn has_nothing_for_unknown_urls() {
+        let dir = TestDir::new("unknown");
+        let fixtures = Fixtures::new(dir.0.clone(), FixtureMode::Record).unwrap();
+        fixtures.save(URL, b"body").unwrap();
+        assert!(fixtures.load("http://statsapi.mlb.com/api/v1/schedule?date=2020-09-02&sportId=1").is_none());
+    }
+
+    #[test]
+    fn never_serves_the_body_of_another_url() {
+        let dir = TestDir::new("collision");
+        let fixtures = Fixtures::new(dir.0.clone(), FixtureMode::Record).unwrap();
+        fixtures.save(URL, b"body").unwrap();
+        // As if another url hashed to the same name
+        let (url_path, _) = fixtures.paths(URL);
+        fs::write(url_path, "http://example.com/other\n").unwrap();
+        assert!(fixtures.load(URL).is_none());
+    }
+
+    #[test]
+    fn only_replays_from_an_existing_directory() {
+        let dir = TestDir::new("missing");
+        assert!(Fixtures::new(dir.0.clone(), FixtureMode::Replay).is_err());
+        // Recording creates it
+        assert!(Fixtures::new(dir.0.clone(), FixtureMode::Record).is_ok());
+        assert!(Fixtures::new(dir.0.clone(), FixtureMode::Replay).is_ok());
+    }
+}
@@ -0,0 +1,357 @@
+extern crate dirs;
+
+use std::ffi::OsStr;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::util::{fnv1a, write_atomically};
+
+pub const DEFAULT_MAX_BYTES: u64 = 256 * 1024 * 1024;
+
+// Lookups only record a new last use once the recorded one is this many seconds old, so reads rarely write
+const LAST_USED_RESOLUTION: u64 = 60;
+
+/// How long a stored response may be served before it has to be revalidated
+#[derive(Clone, Copy, Debug)]
+pub enum Freshness {
+    For(Duration),
+    Forever,
+    /// Always asks the server whether the stored response is still current
+    Revalidate
+}
+
+pub struct CachedResponse {
+    pub(crate) body: Vec<u8>,
+    pub(crate) etag: Option<String>,
+    pub(crate) last_modified: Option<String>,
+    pub(crate) fresh: bool
+}
+
+struct Entry {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    // Seconds since the unix epoch, `None` never expires
+    expires: Option<u64>,
+    last_used: u64
+}
+
+impl Entry {
+    fn parse(text: &str) -> Option<Self> {
+        let mut entry = Entry { url: String::new(), etag: None, last_modified: None, expires: None, last_used: 0 };
+        for line in text.lines() {
+            let (key, value) = match line.find(' ') {
+                Some(split) => { (&line[..split], &line[split + 1..]) }
+                None => { continue }
+            };
+            match key {
+                "url" => { entry.url = value.to_owned() }
+                "etag" => { entry.etag = Some(value.to_owned()) }
+                "last-modified" => { entry.last_modified = Some(value.to_owned()) }
+                "expires" => { entry.expires = if value == "never" { None } else { Some(value.parse().ok()?) } }
+                "used" => { entry.last_used = value.parse().ok()? }
+                _ => {}
+            }
+        }
+        if entry.url.is_empty() { None } else { Some(entry) }
+    }
+
+    fn serialize(&self) -> String {
+        let mut text = format!("url {}\n", self.url);
+        if let Some(etag) = &self.etag {
+            text.push_str(format!("etag {}\n", etag).as_str());
+        }
+        if let Some(last_modified) = &self.last_modified {
+            text.push_str(format!("last-modified {}\n", last_modified).as_str());
+        }
+        match self.expires {
+            Some(expires) => { text.push_str(format!("expires {}\n", expires).as_str()) }
+            None => { text.push_str("expires never\n") }
+        }
+        text.push_str(format!("used {}\n", self.last_used).as_str());
+        text
+    }
+}
+
+/// Stores response bodies on disk keyed by url, next to a small metadata file holding
+/// the validators needed to revalidate them. The least recently used entries are evicted
+/// once the bodies grow past `max_bytes`.
+pub struct HttpCache {
+    dir: PathBuf,
+    max_bytes: u64,
+    // The size of all bodies, counted once on opening and kept up to date by every store,
+    // so that only growing past `max_bytes` lists the directory. Also serializes metadata
+    // updates and eviction between the fetching threads.
+    total_bytes: Mutex<u64>
+}
+
+impl HttpCache {
+    pub fn new(dir: PathBuf, max_bytes: u64) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        let cache = HttpCache { dir, max_bytes, total_bytes: Mutex::new(0) };
+        let mut total = cache.total_bytes.lock().unwrap();
+        cache.evict(&mut total);
+        drop(total);
+        Ok(cache)
+    }
+
+    /// In the per-user cache directory, `$XDG_CACHE_HOME/mlb_game_viewer/http` on Linux
+    pub fn default_dir() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("mlb_game_viewer").join("http"))
+    }
+
+    pub fn lookup(&self, url: &str) -> Option<CachedResponse> {
+        let (meta_path, body_path) = self.paths(url);
+        let now = unix_now();
+        let entry = {
+            let _guard = self.total_bytes.lock().unwrap();
+            let mut entry = Entry::parse(&fs::read_to_string(&meta_path).ok()?)?;
+            // Two urls hashing to the same name is unlikely but must not serve the wrong body
+            if entry.url != url {
+                return None;
+            }
+            if now >= entry.last_used + LAST_USED_RESOLUTION {
+                entry.last_used = now;
+                let _ = write_atomically(&meta_path, entry.serialize().as_bytes());
+            }
+            entry
+        };
+        // Bodies are replaced by renaming, so reading one while another thread stores is safe
+        let body = fs::read(&body_path).ok()?;
+
+        Some(CachedResponse {
+            body,
+            fresh: entry.expires.map(|expires| now < expires).unwrap_or(true),
+            etag: entry.etag,
+            last_modified: entry.last_modified
+        })
+    }
+
+    pub fn store(&self, url: &str, body: &[u8], etag: Option<String>, last_modified: Option<String>, freshness: Freshness) {
+        let mut total = self.total_bytes.lock().unwrap();
+        let (meta_path, body_path) = self.paths(url);
+        let now = unix_now();
+        let entry = Entry { url: url.to_owned(), etag, last_modified, expires: expiry(now, freshness), last_used: now };
+
+        // The body being replaced no longer counts
+        let replaced = fs::metadata(&body_path).map_or(0, |meta| meta.len());
+        *total = total.saturating_sub(replaced);
+        if write_atomically(&body_path, body).is_err() || write_atomically(&meta_path, entry.serialize().as_bytes()).is_err() {
+            let _ = fs::remove_file(&meta_path);
+            let _ = fs::remove_file(&body_path);
+            return;
+        }
+        *total += body.len() as u64;
+        if *total > self.max_bytes {
+            self.evict(&mut total);
+        }
+    }
+
+    /// Restarts the lifetime of an entry the server confirmed to be unchanged, or changes it
+    pub fn refresh(&self, url: &str, freshness: Freshness) {
+        let _guard = self.total_bytes.lock().unwrap();
+        let (meta_path, _) = self.paths(url);
+        if let Some(mut entry) = fs::read_to_string(&meta_path).ok().and_then(|text| Entry::parse(&text)) {
+            let now = unix_now();
+            entry.expires = expiry(now, freshness);
+            entry.last_used = now;
+            let _ = write_atomically(&meta_path, entry.serialize().as_bytes());
+        }
+    }
+
+    fn paths(&self, url: &str) -> (PathBuf, PathBuf) {
+        let name = format!("{:016x}", fnv1a(url.as_bytes()));
+        (self.dir.join(format!("{}.meta", name)), self.dir.join(format!("{}.body", name)))
+    }
+
+    /// Counts the bodies on disk into `total` and removes the least recently used ones past `max_bytes`
+    fn evict(&self, total: &mut u64) {
+        let mut entries = Vec::new();
+        *total = 0;
+        let listing = match fs::read_dir(&self.dir) {
+            Ok(listing) => { listing }
+            Err(_) => { return }
+        };
+        for dir_entry in listing.filter_map(|dir_entry| dir_entry.ok()) {
+            let meta_path = dir_entry.path();
+            if meta_path.extension() != Some(OsStr::new("meta")) {
+                continue;
+            }
+            let body_path = meta_path.with_extension("body");
+            let size = fs::metadata(&body_path).map(|meta| meta.len()).unwrap_or(0);
+            let last_used = fs::read_to_string(&meta_path).ok()
+                .and_then(|text| Entry::parse(&text))
+                .map_or(0, |entry| entry.last_used);
+            *total += size;
+            entries.push((last_used, size, meta_path, body_path));
+        }
+
+        if *total <= self.max_bytes {
+            return;
+        }
+        entries.sort_by_key(|(last_used, ..)| *last_used);
+        for (_, size, meta_path, body_path) in entries {
+            if *total <= self.max_bytes {
+                break;
+            }
+            let _ = fs::remove_file(meta_path);
+            let _ = fs::remove_file(body_path);
+            *total -= size;
+        }
+    }
+}
+
+fn expiry(now: u64, freshness: Freshness) -> Option<u64> {
+    match freshness {
+        Freshness::For(duration) => { Some(now + duration.as_secs()) }
+        Freshness::Forever => { None }
+        Freshness::Revalidate => { Some(now) }
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_secs()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A fresh directory per test, removed again when the test is done
+    struct TestDir(PathBuf);
+
+    impl TestDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("mlb_game_viewer_http_cache_{}_{}", std::process::id(), name));
+            let _ = fs::remove_dir_all(&dir);
+            TestDir(dir)
+        }
+    }
+
+    impl Drop for TestDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn write_entry(cache: &HttpCache, url: &str, body: &[u8], last_used: u64) {
+        let (meta_path, body_path) = cache.paths(url);
+        let entry = Entry { url: url.to_owned(), etag: None, last_modified: None, expires: None, last_used };
+        write_atomically(&body_path, body).unwrap();
+        write_atomically(&meta_path, entry.serialize().as_bytes()).unwrap();
+    }
+
+    fn last_used(cache: &HttpCache, url: &str) -> u64 {
+        let (meta_path, _) = cache.paths(url);
+        Entry::parse(&fs::read_to_string(meta_path).unwrap()).unwrap().last_used
+    }
+
+    #[test]
+    fn entries_survive_a_round_trip() {
+        let entry = Entry {
+            url: "http://statsapi.mlb.com/api/v1/schedule?date=2020-09-01&sportId=1".to_owned(),
+            etag: Some("W/\"5e-1a2b\"".to_owned()),
+            last_modified: Some("Tue, 01 Sep 2020 17:10:00 GMT".to_owned()),
+            expires: Some(1_598_980_260),
+            last_used: 1_598_980_200
+        };
+        let parsed = Entry::parse(&entry.serialize()).unwrap();
+        assert_eq!(parsed.url, entry.url);
+        assert_eq!(parsed.etag, entry.etag);
+        assert_eq!(parsed.last_modified, entry.last_modified);
+        assert_eq!(parsed.expires, entry.expires);
+        assert_eq!(parsed.last_used, entry.last_used);
+    }
+
+    #[test]
+    fn entries_without_expiry_never_expire() {
+        let entry = Entry::parse("url http://example.com/a.jpg\nexpires never\nused 12\n").unwrap();
+        assert_eq!(entry.expires, None);
+        assert_eq!(entry.etag, None);
+        assert_eq!(entry.last_used, 12);
+    }
+
+    #[test]
+    fn rejects_broken_entries() {
+        assert!(Entry::parse("").is_none());
+        assert!(Entry::parse("etag \"abc\"\nused 12\n").is_none());
+        assert!(Entry::parse("url http://example.com/a.jpg\nexpires soon\n").is_none());
+        assert!(Entry::parse("url http://example.com/a.jpg\nused yesterday\n").is_none());
+    }
+
+    #[test]
+    fn serves_what_it_stored() {
+        let dir = TestDir::new("serves");
+        let cache = HttpCache::new(dir.0.clone(), DEFAULT_MAX_BYTES).unwrap();
+        cache.store("http://example.com/fresh", b"fresh", Some("\"1\"".to_owned()), None, Freshness::For(Duration::from_secs(60)));
+        cache.store("http://example.com/stale", b"stale", None, None, Freshness::Revalidate);
+
+        let fresh = cache.lookup("http://example.com/fresh").unwrap();
+        assert_eq!(fresh.body, b"fresh");
+        assert_eq!(fresh.etag.as_deref(), Some("\"1\""));
+        assert!(fresh.fresh);
+        assert!(!cache.lookup("http://example.com/stale").unwrap().fresh);
+        assert!(cache.lookup("http://example.com/missing").is_none());
+    }
+
+    #[test]
+    fn evicts_the_least_recently_used_entries() {
+        let dir = TestDir::new("evicts");
+        let now = unix_now();
+        let seeded = HttpCache::new(dir.0.clone(), DEFAULT_MAX_BYTES).unwrap();
+        write_entry(&seeded, "http://example.com/old", b"123456", now - 200);
+        write_entry(&seeded, "http://example.com/recent", b"12", now - 100);
+
+        // Counts what an earlier run left behind
+        let cache = HttpCache::new(dir.0.clone(), 10).unwrap();
+        cache.store("http://example.com/new", b"1234", None, None, Freshness::Forever);
+
+        assert!(cache.lookup("http://example.com/old").is_none());
+        assert!(cache.lookup("http://example.com/recent").is_some());
+        assert!(cache.lookup("http://example.com/new").is_some());
+    }
+
+    #[test]
+    fn evicts_past_the_cap_when_opened() {
+        let dir = TestDir::new("opened");
+        let now = unix_now();
+        let seeded = HttpCache::new(dir.0.clone(), DEFAULT_MAX_BYTES).unwrap();
+        write_entry(&seeded, "http://example.com/old", b"123456", now - 200);
+        write_entry(&seeded, "http://example.com/recent", b"12", now - 100);
+
+        let cache = HttpCache::new(dir.0.clone(), 5).unwrap();
+        assert!(cache.lookup("http://example.com/old").is_none());
+        assert!(cache.lookup("http://example.com/recent").is_some());
+    }
+
+    #[test]
+    fn counts_replaced_bodies_once() {
+        let dir = TestDir::new("replaced");
+        let cache = HttpCache::new(dir.0.clone(), 10).unwrap();
+        cache.store("http://example.com/a", b"123456", None, None, Freshness::Forever);
+        cache.store("http://example.com/a", b"123456", None, None, Freshness::Forever);
+        cache.store("http://example.com/b", b"123", None, None, Freshness::Forever);
+
+        assert_eq!(*cache.total_bytes.lock().unwrap(), 9);
+        assert!(cache.lookup("http://example.com/a").is_some());
+        assert!(cache.lookup("http://example.com/b").is_some());
+    }
+
+    #[test]
+    fn lookups_only_record_uses_that_move_the_last_one() {
+        let dir = TestDir::new("lookups");
+        let cache = HttpCache::new(dir.0.clone(), DEFAULT_MAX_BYTES).unwrap();
+        let now = unix_now();
+        write_entry(&cache, "http://example.com/recent", b"body", now - 10);
+        write_entry(&cache, "http://example.com/old", b"body", now - 2 * LAST_USED_RESOLUTION);
+
+        cache.lookup("http://example.com/recent").unwrap();
+        cache.lookup("http://example.com/old").unwrap();
+
+        assert_eq!(last_used(&cache, "http://example.com/recent"), now - 10);
+        assert!(last_used(&cache, "http://example.com/old") >= now);
+    }
+}
@@ -1,47 +1,588 @@
 mod ui;
 mod data;
+mod http_cache;
+mod fixtures;
+mod cli;
+mod statsapi;
+mod player;
+mod logging;
+mod config;
+mod util;
 
 extern crate sdl2;
 extern crate image;
 
-use sdl2::event::Event;
+use sdl2::event::{Event, WindowEvent};
 use sdl2::keyboard::Keycode;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::sync::Arc;
+use std::fmt;
+use std::collections::HashMap;
 use sdl2::rect::{Rect};
+use sdl2::video::FullscreenType;
+use sdl2::render::Texture;
+use sdl2::ttf::{Font, Sdl2TtfContext};
+use std::hash::Hash;
 use sdl2::rwops::RWops;
 use sdl2::image::{InitFlag, LoadTexture};
-use std::path::{PathBuf};
+use chrono::NaiveDate;
 
 use ui::*;
 
 pub fn main() {
-    let mut sdl_renderer = SDL2Renderer::new();
-    let mut event_pump = sdl_renderer.context.sdl_context.event_pump().unwrap();
+    let options = cli::Options::from_args();
+    logging::init(options.log_level);
+
+    // Command-line options override the configuration file, which overrides the defaults
+    let config_path = options.config.or_else(config::Config::default_path);
+    let config = match &config_path {
+        Some(path) => {
+            config::Config::load(path).unwrap_or_else(|err| {
+                eprintln!("Invalid configuration, {}", err);
+                std::process::exit(2);
+            })
+        }
+        None => { config::Config::default() }
+    };
 
-    let mut loading_scene = make_loading_scene(&sdl_renderer);
-    loading_scene.accept_visitor(&mut sdl_renderer);
-    sdl_renderer.present();
+    let fixtures = options.fixtures.map(|(dir, mode)| {
+        fixtures::Fixtures::new(dir.clone(), mode).unwrap_or_else(|err| {
+            eprintln!("Could not use fixtures in {}: {}", dir.display(), err);
+            std::process::exit(2);
+        })
+    });
 
-    let mut loaded_scene = make_loaded_scene(&sdl_renderer);
+    let player = player::ExternalPlayer::new(options.player.as_str());
 
+    let favorites = if options.favorites.is_empty() {
+        data::Teams::new(&config.favorites.iter().map(|team| team.as_str()).collect::<Vec<_>>())
+    } else {
+        options.favorites
+    };
+    let has_favorites = !favorites.is_empty();
+    let mut only_favorites = (options.only_favorites || config.only_favorites) && has_favorites;
+
+    let cache_dir = config.cache.dir.or_else(http_cache::HttpCache::default_dir);
+    let cache = match (config.cache.enabled, cache_dir) {
+        (true, Some(dir)) => {
+            match http_cache::HttpCache::new(dir.clone(), config.cache.max_bytes) {
+                Ok(cache) => { Some(cache) }
+                Err(err) => {
+                    log::warn!("Running without a cache, {} could not be created: {}", dir.display(), err);
+                    None
+                }
+            }
+        }
+        _ => { None }
+    };
+
+    let context = Context {
+        fetcher: Arc::new(data::Fetcher::new(options.query, cache, fixtures)),
+        teams: TeamFilter { shown: options.teams, favorites },
+        theme: config.theme,
+        keys: config.keys,
+        live_poll_interval: config.live_poll_interval
+    };
+    let keys = &context.keys;
+
+    // The geometry the window was last closed with wins over the configured one
+    let state_path = config::WindowState::default_path();
+    let saved = match &state_path {
+        Some(path) => {
+            config::WindowState::load(path).unwrap_or_else(|err| {
+                log::warn!("Ignoring the saved window geometry in {}: {}", path.display(), err);
+                config::WindowState::default()
+            })
+        }
+        None => { config::WindowState::default() }
+    };
+
+    let fullscreen = options.fullscreen || config.window.fullscreen;
+    let window_size = options.window_size.or_else(|| saved.size()).unwrap_or(config.window.size);
+    let window_position = saved.position().or(config.window.position);
+    // Outlives the renderer, which keeps the fonts loaded from it
+    let font_context = sdl2::ttf::init().unwrap();
+    let mut sdl_renderer = SDL2Renderer::new(&font_context, window_size, window_position, fullscreen);
+    let mut event_pump = sdl_renderer.context.sdl_context.event_pump().unwrap();
+
+    let mut date = options.date.unwrap_or_else(data::league_today);
+    let mut scene = Scene::load(&sdl_renderer, &context, date, only_favorites);
+
+    let mut redraw = true;
     'running: loop {
-        for event in event_pump.poll_iter() {
+        // Waking up now and then picks up what the workers sent without any event coming in
+        let timeout = if scene.is_animating() { LOADING_FRAME_MS as u32 } else { IDLE_WAKE_MS };
+        let first = event_pump.wait_event_timeout(timeout);
+        for event in first.into_iter().chain(event_pump.poll_iter()) {
             match event {
-                Event::Quit {..} | Event::KeyDown { keycode: Some(Keycode::Escape), .. } => {
+                Event::Quit {..} => {
+                    break 'running
+                },
+                Event::Window { win_event: WindowEvent::SizeChanged(..), .. } => {
+                    scene.reflow(&sdl_renderer);
+                },
+                Event::Window { win_event: WindowEvent::Exposed, .. } => {
+                    redraw = true;
+                },
+                _ if keys.fullscreen.is_pressed(&event) => {
+                    // The window reports its new size once it got there, which reflows the scene
+                    sdl_renderer.toggle_fullscreen();
+                },
+                // The quit key leaves the detail scenes first and only quits from the schedule
+                _ if keys.quit.is_pressed(&event) && !scene.is_detail() => {
                     break 'running
                 },
-                _ => loaded_scene.accept_visitor(&mut SDL2EventPropagator::new(event))
+                _ => {
+                    let mut propagator = SDL2EventPropagator::new(event);
+                    scene.root().accept_visitor(&mut propagator);
+                    if propagator.consumed { continue; }
+                    // Keys may switch scenes, and going back shows one that was already drawn and is clean
+                    if let Event::KeyDown { .. } = propagator.event {
+                        redraw = true;
+                    }
+
+                    if scene.is_detail() {
+                        if keys.back.is_pressed(&propagator.event) || (keys.standings.is_pressed(&propagator.event) && matches!(scene, Scene::Standings { .. })) {
+                            scene = scene.leave();
+                        } else if keys.open.is_pressed(&propagator.event) {
+                            scene = scene.open(&sdl_renderer, &context, date);
+                        } else if keys.plays.is_pressed(&propagator.event) {
+                            scene = scene.open_plays(&sdl_renderer, &context, date);
+                        } else if keys.watch.is_pressed(&propagator.event) {
+                            if let (Some(player), Some(url)) = (&player, scene.selected_highlight()) {
+                                if let Err(err) = player.play(url.as_str()) {
+                                    log::warn!("Could not start the player for {}: {}", url, err);
+                                }
+                            }
+                        }
+                    } else if let Some(new_date) = navigate_date(keys, &propagator.event, date) {
+                        date = new_date;
+                        scene = Scene::load(&sdl_renderer, &context, date, only_favorites);
+                    } else if let Scene::Error(_) = scene {
+                        if keys.retry.is_pressed(&propagator.event) {
+                            scene = Scene::load(&sdl_renderer, &context, date, only_favorites);
+                        }
+                    } else if keys.open.is_pressed(&propagator.event) {
+                        scene = scene.open(&sdl_renderer, &context, date);
+                    } else if keys.standings.is_pressed(&propagator.event) {
+                        scene = scene.open_standings(&sdl_renderer, &context, date);
+                    } else if keys.filter.is_pressed(&propagator.event) && has_favorites {
+                        only_favorites = !only_favorites;
+                        scene.show_only_favorites(&sdl_renderer, &context, date, only_favorites);
+                    }
+                }
             }
         }
 
-        loaded_scene.accept_visitor( &mut sdl_renderer);
-        sdl_renderer.present();
-        ::std::thread::sleep(Duration::new(0, 1_000_000_000u32 / 60));
+        scene.update(&sdl_renderer, &context, date);
+        if redraw || scene.is_dirty() {
+            sdl_renderer.clear();
+            scene.root().accept_visitor(&mut sdl_renderer);
+            sdl_renderer.present();
+            redraw = false;
+        }
     }
+
+    // A fullscreen window's geometry is the screen's, keep the one it had before
+    if let (Some(path), false) = (&state_path, sdl_renderer.is_fullscreen()) {
+        let (size, position) = sdl_renderer.window_geometry();
+        // A size given with --window only holds for this run
+        let size = if options.window_size.is_some() { saved.size() } else { Some(size) };
+        if let Err(err) = config::WindowState::new(size, position).save(path) {
+            log::warn!("Could not save the window geometry to {}: {}", path.display(), err);
+        }
+    }
+}
+
+/// Maps the day navigation keys to the date they lead to, if the event is one of them
+fn navigate_date(keys: &config::KeyBindings, event: &Event, date: NaiveDate) -> Option<NaiveDate> {
+    if keys.previous_day.is_pressed(event) {
+        date.pred_opt()
+    } else if keys.next_day.is_pressed(event) {
+        date.succ_opt()
+    } else {
+        None
+    }
+}
+
+const LOADING_FRAME_MS: u128 = 300;
+/// How long the loop waits for an event when nothing is animating
+const IDLE_WAKE_MS: u32 = 250;
+
+/// The teams whose games the schedule lists and the favorites among them it frames and lists first
+struct TeamFilter {
+    // Every team when empty
+    shown: data::Teams,
+    favorites: data::Teams
 }
 
-fn make_splash(sdl_renderer: &SDL2Renderer) -> Image {
-    let splash_path = PathBuf::from("./images/background.png");
+/// What the scenes are fetched and built with, set up by `main` from the options and the configuration
+struct Context {
+    fetcher: Arc<data::Fetcher>,
+    teams: TeamFilter,
+    theme: Theme,
+    keys: config::KeyBindings,
+    // How often the games in progress are polled
+    live_poll_interval: Duration
+}
+
+enum Scene {
+    Loading {
+        root: LayoutItem,
+        // Of the schedule being fetched, polled again once it arrived
+        url: String,
+        receiver: Receiver<Result<Vec<data::GameModel>, data::Error>>,
+        started: Instant,
+        frame: u32,
+        only_favorites: bool
+    },
+    Schedule {
+        root: LayoutItem,
+        games: Vec<data::GameModel>,
+        thumbnails: Receiver<data::Thumbnail>,
+        poller: data::GamePoller,
+        only_favorites: bool
+    },
+    /// The linescore and highlights of one game, keeping the schedule it was opened from to return to
+    Linescore {
+        root: LayoutItem,
+        game_pk: u64,
+        shown: (data::GameStatus, data::Linescore, Vec<data::Highlight>),
+        thumbnails: Receiver<data::Thumbnail>,
+        previous: Box<Scene>
+    },
+    /// The box score of one game, fetched when opened from its linescore
+    Boxscore {
+        root: LayoutItem,
+        title: String,
+        receiver: Option<Receiver<Result<data::Boxscore, data::Error>>>,
+        previous: Box<Scene>
+    },
+    /// The play-by-play of one game, fetched when opened from its linescore
+    Plays {
+        root: LayoutItem,
+        title: String,
+        receiver: Option<Receiver<Result<Vec<data::Play>, data::Error>>>,
+        previous: Box<Scene>
+    },
+    /// The division standings on the day of the scene it was opened from
+    Standings {
+        root: LayoutItem,
+        receiver: Option<Receiver<Result<Vec<data::DivisionStandings>, data::Error>>>,
+        previous: Box<Scene>
+    },
+    Error(LayoutItem)
+}
+
+impl Scene {
+    /// Starts fetching the games for `date` in the background and shows the loading scene meanwhile
+    fn load(renderer: &SDL2Renderer, context: &Context, date: NaiveDate, only_favorites: bool) -> Self {
+        match data::make_url_for_date(&context.fetcher.query, date) {
+            Ok(url) => {
+                Scene::Loading {
+                    root: make_loading_scene(renderer, context, 0),
+                    receiver: data::spawn_fetch_games(&context.fetcher, url.clone()),
+                    url,
+                    started: Instant::now(),
+                    frame: 0,
+                    only_favorites
+                }
+            }
+            Err(err) => { Scene::Error(make_error_scene(renderer, context, date, &err)) }
+        }
+    }
+
+    /// Switches between listing every game and only the favorite teams' games,
+    /// staying on the selected game if it is still listed
+    fn show_only_favorites(&mut self, renderer: &SDL2Renderer, context: &Context, date: NaiveDate, only: bool) {
+        match self {
+            Scene::Loading { only_favorites, .. } => { *only_favorites = only }
+            Scene::Schedule { root, games, only_favorites, .. } => {
+                *only_favorites = only;
+                let mut finder = SelectedKeyFinder::new();
+                root.accept_visitor(&mut finder);
+                *root = make_loaded_scene(renderer, context, date, games, only);
+                if let Some(key) = finder.key {
+                    root.accept_visitor(&mut KeySelector::new(key));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Lays this scene and the ones underneath it out again to fill the resized viewport
+    fn reflow(&mut self, renderer: &SDL2Renderer) {
+        self.root().set_position(Position::new(Point::origin(), renderer.viewport_size()));
+        match self {
+            Scene::Linescore { previous, .. } | Scene::Boxscore { previous, .. } | Scene::Plays { previous, .. } | Scene::Standings { previous, .. } => {
+                previous.reflow(renderer)
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether anything in the scene changed since it was last drawn
+    fn is_dirty(&mut self) -> bool {
+        let mut finder = DirtyFinder::new();
+        self.root().accept_visitor(&mut finder);
+        finder.dirty
+    }
+
+    /// Whether the scene changes on its own and needs the loop to wake up in time for its next frame
+    fn is_animating(&self) -> bool {
+        matches!(self, Scene::Loading { .. })
+    }
+
+    fn root(&mut self) -> &mut LayoutItem {
+        match self {
+            Scene::Loading { root, .. } => { root }
+            Scene::Schedule { root, .. } => { root }
+            Scene::Linescore { root, .. } => { root }
+            Scene::Boxscore { root, .. } => { root }
+            Scene::Plays { root, .. } => { root }
+            Scene::Standings { root, .. } => { root }
+            Scene::Error(root) => { root }
+        }
+    }
+
+    /// Detail scenes are left for the scene they were opened from rather than navigated by date
+    fn is_detail(&self) -> bool {
+        matches!(self, Scene::Linescore { .. } | Scene::Boxscore { .. } | Scene::Plays { .. } | Scene::Standings { .. })
+    }
+
+    /// Opens the linescore of the selected game from the schedule and the box score from the linescore,
+    /// staying on the current scene if there is nothing to open
+    fn open(mut self, renderer: &SDL2Renderer, context: &Context, date: NaiveDate) -> Self {
+        match &self {
+            Scene::Schedule { .. } => {
+                let mut finder = SelectedKeyFinder::new();
+                self.root().accept_visitor(&mut finder);
+                let linescore = match (&self, finder.key) {
+                    (Scene::Schedule { games, .. }, Some(key)) => {
+                        games.iter()
+                            .find(|game| game.game_pk == key)
+                            .map(|game| (
+                                make_linescore_scene(renderer, context, date, game),
+                                game.game_pk,
+                                (game.status, game.linescore.clone(), game.highlights.clone()),
+                                data::spawn_fetch_highlight_thumbnails(&context.fetcher, game, HighlightItemFactory::THUMBNAIL_SIZE.dimensions(), renderer.pixel_ratio())
+                            ))
+                    }
+                    _ => { None }
+                };
+                match linescore {
+                    Some((root, game_pk, shown, thumbnails)) => {
+                        Scene::Linescore { root, game_pk, shown, thumbnails, previous: Box::new(self) }
+                    }
+                    None => { self }
+                }
+            }
+            Scene::Linescore { game_pk, previous, .. } => {
+                let title = matchup_title(previous, *game_pk);
+                Scene::Boxscore {
+                    root: make_boxscore_scene(renderer, context, date, &title, None),
+                    receiver: Some(data::spawn_fetch_boxscore(&context.fetcher, context.fetcher.query.url_for_boxscore(*game_pk))),
+                    title,
+                    previous: Box::new(self)
+                }
+            }
+            _ => { self }
+        }
+    }
+
+    /// Opens the play-by-play from the linescore
+    fn open_plays(self, renderer: &SDL2Renderer, context: &Context, date: NaiveDate) -> Self {
+        match &self {
+            Scene::Linescore { game_pk, previous, .. } => {
+                let title = matchup_title(previous, *game_pk);
+                Scene::Plays {
+                    root: make_plays_scene(renderer, context, date, &title, None),
+                    receiver: Some(data::spawn_fetch_plays(&context.fetcher, context.fetcher.query.url_for_live_feed(*game_pk))),
+                    title,
+                    previous: Box::new(self)
+                }
+            }
+            _ => { self }
+        }
+    }
+
+    /// Opens the standings on `date` on top of the current scene
+    fn open_standings(self, renderer: &SDL2Renderer, context: &Context, date: NaiveDate) -> Self {
+        Scene::Standings {
+            root: make_standings_scene(renderer, context, date, None),
+            receiver: Some(data::spawn_fetch_standings(&context.fetcher, date)),
+            previous: Box::new(self)
+        }
+    }
+
+    /// The url of the highlight selected in the linescore scene
+    fn selected_highlight(&mut self) -> Option<String> {
+        let mut finder = SelectedKeyFinder::new();
+        self.root().accept_visitor(&mut finder);
+        match (&*self, finder.key) {
+            (Scene::Linescore { game_pk, previous, .. }, Some(index)) => {
+                match previous.as_ref() {
+                    Scene::Schedule { games, .. } => {
+                        games.iter()
+                            .find(|game| game.game_pk == *game_pk)
+                            .and_then(|game| game.highlights.get(index as usize))
+                            .map(|highlight| highlight.url.clone())
+                    }
+                    _ => { None }
+                }
+            }
+            _ => { None }
+        }
+    }
+
+    fn leave(self) -> Self {
+        match self {
+            Scene::Linescore { previous, .. } | Scene::Boxscore { previous, .. } | Scene::Plays { previous, .. } | Scene::Standings { previous, .. } => {
+                *previous
+            }
+            _ => { self }
+        }
+    }
+
+    /// Advances the loading animation, swaps in the loaded scene once the worker has answered
+    /// and fills in thumbnails and live scores as they arrive
+    fn update(&mut self, renderer: &SDL2Renderer, context: &Context, date: NaiveDate) {
+        let next = match self {
+            Scene::Loading { root, url, receiver, started, frame, only_favorites } => {
+                match receiver.try_recv() {
+                    Ok(Ok(games)) => {
+                        Some(Scene::Schedule {
+                            root: make_loaded_scene(renderer, context, date, &games, *only_favorites),
+                            only_favorites: *only_favorites,
+                            thumbnails: data::spawn_fetch_thumbnails(&context.fetcher, &games, &GameItemFactory::thumbnail_sizes().map(|size| size.dimensions()), renderer.pixel_ratio()),
+                            poller: data::spawn_poll_games(&context.fetcher, url.clone(), &games, context.live_poll_interval),
+                            games
+                        })
+                    }
+                    Ok(Err(err)) => { Some(Scene::Error(make_error_scene(renderer, context, date, &err))) }
+                    Err(TryRecvError::Disconnected) => {
+                        Some(Scene::Error(make_error_scene(renderer, context, date, &data::Error::Stopped)))
+                    }
+                    Err(TryRecvError::Empty) => {
+                        let current = (started.elapsed().as_millis() / LOADING_FRAME_MS) as u32;
+                        if current != *frame {
+                            *frame = current;
+                            *root = make_loading_scene(renderer, context, current);
+                        }
+                        None
+                    }
+                }
+            }
+            Scene::Schedule { root, games, thumbnails, poller, .. } => {
+                for thumbnail in thumbnails.try_iter() {
+                    if let Some(game) = games.iter_mut().find(|game| game.game_pk == thumbnail.game_pk) {
+                        game.images.insert(thumbnail.url, thumbnail.image);
+                        root.accept_visitor(&mut GameItemFactory::thumbnail_setter(game, renderer.pixel_ratio()));
+                    }
+                }
+                for polled in poller.updates.try_iter() {
+                    for update in polled {
+                        if let Some(game) = games.iter_mut().find(|game| game.game_pk == update.game_pk) {
+                            if game.apply_update(update) {
+                                root.accept_visitor(&mut ListItemReplacer::new(GameItemFactory::make(game, &context.teams.favorites, &context.theme, renderer.pixel_ratio())));
+                            }
+                        }
+                    }
+                }
+                None
+            }
+            Scene::Linescore { root, game_pk, shown, thumbnails, previous } => {
+                // The schedule keeps polling underneath, redraw whenever it brought news for this game
+                previous.update(renderer, context, date);
+                if let Scene::Schedule { games, .. } = previous.as_mut() {
+                    if let Some(game) = games.iter_mut().find(|game| game.game_pk == *game_pk) {
+                        if shown.0 != game.status || shown.1 != game.linescore || shown.2 != game.highlights {
+                            *shown = (game.status, game.linescore.clone(), game.highlights.clone());
+                            // Stay on the highlight the viewer was looking at
+                            let mut finder = SelectedKeyFinder::new();
+                            root.accept_visitor(&mut finder);
+                            *root = make_linescore_scene(renderer, context, date, game);
+                            if let Some(key) = finder.key {
+                                root.accept_visitor(&mut KeySelector::new(key));
+                            }
+                        }
+                        for thumbnail in thumbnails.try_iter() {
+                            game.images.insert(thumbnail.url.clone(), thumbnail.image);
+                            for (index, highlight) in game.highlights.iter().enumerate() {
+                                if highlight.best_cut(HighlightItemFactory::THUMBNAIL_SIZE.dimensions(), renderer.pixel_ratio()).map(|cut| &cut.src) == Some(&thumbnail.url) {
+                                    let image = game.highlight_thumbnail(highlight, HighlightItemFactory::THUMBNAIL_SIZE.dimensions(), renderer.pixel_ratio());
+                                    root.accept_visitor(&mut ItemImageSetter::new(index as u64, image.clone(), image));
+                                }
+                            }
+                        }
+                    }
+                }
+                None
+            }
+            Scene::Boxscore { root, title, receiver, previous } => {
+                previous.update(renderer, context, date);
+                if let Some(answer) = take_answer(receiver) {
+                    *root = make_boxscore_scene(renderer, context, date, title, Some(answer));
+                }
+                None
+            }
+            Scene::Plays { root, title, receiver, previous } => {
+                previous.update(renderer, context, date);
+                if let Some(answer) = take_answer(receiver) {
+                    *root = make_plays_scene(renderer, context, date, title, Some(answer));
+                }
+                None
+            }
+            Scene::Standings { root, receiver, previous } => {
+                previous.update(renderer, context, date);
+                if let Some(answer) = take_answer(receiver) {
+                    *root = make_standings_scene(renderer, context, date, Some(answer));
+                }
+                None
+            }
+            _ => { None }
+        };
+
+        if let Some(next) = next {
+            *self = next;
+        }
+    }
+}
+
+/// The answer of a worker once it arrived, after which the receiver is dropped
+fn take_answer<T>(receiver: &mut Option<Receiver<Result<T, data::Error>>>) -> Option<Result<T, data::Error>> {
+    let answer = match receiver {
+        Some(pending) => {
+            match pending.try_recv() {
+                Ok(answer) => { Some(answer) }
+                Err(TryRecvError::Disconnected) => { Some(Err(data::Error::Stopped)) }
+                Err(TryRecvError::Empty) => { None }
+            }
+        }
+        None => { None }
+    };
+    if answer.is_some() {
+        *receiver = None;
+    }
+    answer
+}
+
+/// "Away at Home" for the game in the schedule underneath a detail scene
+fn matchup_title(schedule: &Scene, game_pk: u64) -> String {
+    match schedule {
+        Scene::Schedule { games, .. } => {
+            games.iter()
+                .find(|game| game.game_pk == game_pk)
+                .map(|game| format!("{} at {}", game.away_team, game.home_team))
+                .unwrap_or_default()
+        }
+        _ => { String::new() }
+    }
+}
+
+fn make_splash(sdl_renderer: &SDL2Renderer, theme: &Theme) -> Image {
+    let splash_path = theme.background.clone();
     if !splash_path.exists() {
         panic!("{} image could not be found", splash_path.to_str().unwrap());
     }
@@ -49,11 +590,13 @@ fn make_splash(sdl_renderer: &SDL2Renderer) -> Image {
     splash
 }
 
-fn make_loading_scene(renderer: &SDL2Renderer) -> LayoutItem {
-    let splash = make_splash(renderer);
+fn make_loading_scene(renderer: &SDL2Renderer, context: &Context, frame: u32) -> LayoutItem {
+    let splash = make_splash(renderer, &context.theme);
 
+    // Pad with spaces so the centered text does not shift as the dots come and go
+    let dots = (frame % 4) as usize;
     let loading_message = Text {
-        content: "Loading...".to_owned(),
+        content: format!("Loading{}{}", ".".repeat(dots), " ".repeat(3 - dots)),
         size: 32,
         pos: Position::new(Point::origin(),Size::new(200, 50)),
         color: Color::new(0, 0, 255)
@@ -66,92 +609,446 @@ fn make_loading_scene(renderer: &SDL2Renderer) -> LayoutItem {
     LayoutItem::Layout(Box::new(c_layout))
 }
 
-fn make_loaded_scene(renderer: &SDL2Renderer) -> LayoutItem {
-    let url = data::make_url_for_date(String::new());
-    let games = data::fetch_games(url);
+const DATE_HEADER_HEIGHT: u32 = 60;
+
+fn make_date_header(date: NaiveDate, width: u32, theme: &Theme) -> Text {
+    Text {
+        content: date.format("%A, %B %-d, %Y").to_string(),
+        size: 36,
+        pos: Position::new(Point::origin(), Size::new(width, DATE_HEADER_HEIGHT)),
+        color: theme.text.clone()
+    }
+}
+
+fn make_error_scene(renderer: &SDL2Renderer, context: &Context, date: NaiveDate, error: &dyn fmt::Display) -> LayoutItem {
+    let viewport = renderer.viewport_size();
+
+    let message = Text {
+        content: format!("Could not load games: {}", error),
+        size: 24,
+        pos: Position::new(Point::origin(), Size::new(viewport.w, 90)),
+        color: Color::new(255, 80, 80)
+    };
+
+    let hint = Text {
+        content: format!("Press {} to retry", context.keys.retry.describe()),
+        size: 18,
+        pos: Position::new(Point::origin(), Size::new(viewport.w, 30)),
+        color: context.theme.text.clone()
+    };
+
+    let mut message_layout = VBoxLayout::new();
+    message_layout.set_position(Position::new(Point::origin(), Size::new(viewport.w, 0)));
+    message_layout.add_child(LayoutItem::Widget(WidgetType::Text(message)));
+    message_layout.add_child(LayoutItem::Widget(WidgetType::Text(hint)));
+
+    make_dated_scene(renderer, context, date, LayoutItem::Layout(Box::new(message_layout)))
+}
+
+/// Lists the games of the chosen teams with the favorite teams' games first, or only them
+fn make_loaded_scene(renderer: &SDL2Renderer, context: &Context, date: NaiveDate, games: &[data::GameModel], only_favorites: bool) -> LayoutItem {
+    let viewport = renderer.viewport_size();
+    let teams = &context.teams;
+
+    let chosen: Vec<&data::GameModel> = games.iter().filter(|game| teams.shown.is_empty() || teams.shown.plays_in(game)).collect();
+    let mut shown: Vec<&data::GameModel> = chosen.iter().copied().filter(|game| !only_favorites || teams.favorites.plays_in(game)).collect();
+    // Stable, so the games keep the schedule's order otherwise
+    shown.sort_by_key(|game| !teams.favorites.plays_in(game));
+
+    if shown.is_empty() {
+        let no_games = Text {
+            content: if games.is_empty() {
+                "No games scheduled"
+            } else if chosen.is_empty() {
+                "No games of the chosen teams"
+            } else {
+                "No games of your favorite teams"
+            }.to_owned(),
+            size: 32,
+            pos: Position::new(Point::origin(), Size::new(viewport.w, 50)),
+            color: context.theme.text.clone()
+        };
+        return make_dated_scene(renderer, context, date, LayoutItem::Widget(WidgetType::Text(no_games)));
+    }
+
+    // Centered on the selected game once the scene gives the list its width
+    let mut list_layout = ListLayout::new(GameItemFactory::ITEM_SIZE);
+    for model in shown {
+        list_layout.add_item(GameItemFactory::make(model, &teams.favorites, &context.theme, renderer.pixel_ratio()));
+    }
+
+    make_dated_scene(renderer, context, date, LayoutItem::Layout(Box::new(list_layout)))
+}
+
+// Room for three highlights below the linescore
+const HIGHLIGHTS_HEIGHT: u32 = 240;
+
+fn make_linescore_scene(renderer: &SDL2Renderer, context: &Context, date: NaiveDate, game: &data::GameModel) -> LayoutItem {
+    let viewport = renderer.viewport_size();
+
+    let matchup = Text {
+        content: format!("{} at {}", game.away_team, game.home_team),
+        size: 28,
+        pos: Position::new(Point::origin(), Size::new(viewport.w, 50)),
+        color: context.theme.text.clone()
+    };
+
+    let state = Text {
+        content: match (&game.status, &game.inning) {
+            (data::GameStatus::InProgress, Some(inning)) => { inning.to_string() }
+            (status, _) => { status.to_string() }
+        },
+        size: 20,
+        pos: Position::new(Point::origin(), Size::new(viewport.w, 30)),
+        color: context.theme.muted_text.clone()
+    };
 
-    let splash = make_splash(renderer);
+    let grid = LinescoreFactory::make(game, &context.theme);
+    let mut grid_layout = CenteredLayout::new(Position::new(Point::origin(), Size::new(viewport.w, grid.position().size.h)));
+    grid_layout.add_child(grid);
 
-    let mut list_layout = ListLayout::new(Size::new(200, 300));
-    for model in games {
-        list_layout.add_item(GameItemFactory::make(model));
+    let hint = Text {
+        content: {
+            let keys = &context.keys;
+            let watch = if game.highlights.is_empty() {
+                String::new()
+            } else {
+                format!(", {} to watch the selected highlight", keys.watch.describe())
+            };
+            format!("Press {} for the box score, {} for the plays{}, {} to go back", keys.open.describe(), keys.plays.describe(), watch, keys.back.describe())
+        },
+        size: 18,
+        pos: Position::new(Point::origin(), Size::new(viewport.w, 40)),
+        color: context.theme.text.clone()
+    };
+
+    let mut detail_layout = VBoxLayout::new();
+    detail_layout.set_position(Position::new(Point::origin(), Size::new(viewport.w, 0)));
+    detail_layout.add_child(LayoutItem::Widget(WidgetType::Text(matchup)));
+    detail_layout.add_child(LayoutItem::Widget(WidgetType::Text(state)));
+    detail_layout.add_child(LayoutItem::Layout(Box::new(grid_layout)));
+    if !game.highlights.is_empty() {
+        let mut list_layout = VListLayout::new(HighlightItemFactory::ITEM_SIZE, HIGHLIGHTS_HEIGHT);
+        for (index, highlight) in game.highlights.iter().enumerate() {
+            let thumbnail = game.highlight_thumbnail(highlight, HighlightItemFactory::THUMBNAIL_SIZE.dimensions(), renderer.pixel_ratio());
+            list_layout.add_item(HighlightItemFactory::make(index, highlight, thumbnail, &context.theme));
+        }
+        detail_layout.add_child(LayoutItem::Layout(Box::new(list_layout)));
     }
+    detail_layout.add_child(LayoutItem::Widget(WidgetType::Text(hint)));
+
+    make_dated_scene(renderer, context, date, LayoutItem::Layout(Box::new(detail_layout)))
+}
+
+const DETAIL_TITLE_HEIGHT: u32 = 50;
+const DETAIL_HINT_HEIGHT: u32 = 30;
+
+// Leaves room for the header, title and hint of the detail scenes above some of their content
+const MIN_WINDOW_SIZE: (u32, u32) = (320, DATE_HEADER_HEIGHT + DETAIL_TITLE_HEIGHT + DETAIL_HINT_HEIGHT + 100);
+
+fn make_boxscore_scene(renderer: &SDL2Renderer, context: &Context, date: NaiveDate, title: &str, boxscore: Option<Result<data::Boxscore, data::Error>>) -> LayoutItem {
+    let hint = format!("Scroll with the up and down arrow keys, {} to go back", context.keys.back.describe());
+    make_fetched_scene(renderer, context, date, DetailTexts { title, what: "box score", hint: &hint }, boxscore,
+        |boxscore, size| BoxscoreFactory::make(&boxscore, &context.theme, size))
+}
+
+fn make_plays_scene(renderer: &SDL2Renderer, context: &Context, date: NaiveDate, title: &str, plays: Option<Result<Vec<data::Play>, data::Error>>) -> LayoutItem {
+    let hint = format!("Move through the plays with the up and down arrow keys, {} to go back", context.keys.back.describe());
+    make_fetched_scene(renderer, context, date, DetailTexts { title, what: "plays", hint: &hint }, plays,
+        |plays, size| {
+            if plays.is_empty() {
+                return LayoutItem::Widget(WidgetType::Text(Text {
+                    content: "No plays yet".to_owned(),
+                    size: 24,
+                    pos: Position::new(Point::origin(), size),
+                    color: context.theme.text.clone()
+                }));
+            }
+            let mut list_layout = VListLayout::new(PlayItemFactory::ITEM_SIZE, size.h);
+            for (index, play) in plays.iter().enumerate() {
+                list_layout.add_item(PlayItemFactory::make(index, play, &context.theme));
+            }
+            LayoutItem::Layout(Box::new(list_layout))
+        })
+}
+
+fn make_standings_scene(renderer: &SDL2Renderer, context: &Context, date: NaiveDate, divisions: Option<Result<Vec<data::DivisionStandings>, data::Error>>) -> LayoutItem {
+    let keys = &context.keys;
+    let hint = format!("Scroll with the up and down arrow keys, {} to go back", config::describe_keys(&[&keys.standings, &keys.back]));
+    make_fetched_scene(renderer, context, date, DetailTexts { title: "Standings", what: "standings", hint: &hint }, divisions,
+        |divisions, size| {
+            if divisions.is_empty() {
+                return LayoutItem::Widget(WidgetType::Text(Text {
+                    content: "No standings for this day".to_owned(),
+                    size: 24,
+                    pos: Position::new(Point::origin(), size),
+                    color: context.theme.text.clone()
+                }));
+            }
+            StandingsFactory::make(&divisions, &context.theme, size)
+        })
+}
+
+/// The title of a detail scene, the name of what it fetches in its messages and the hint below its content
+struct DetailTexts<'a> {
+    title: &'a str,
+    what: &'a str,
+    hint: &'a str
+}
+
+/// A titled detail scene showing a loading message until `answer` holds what the worker fetched,
+/// then the content `make` builds from it to fit the given size
+fn make_fetched_scene<T>(renderer: &SDL2Renderer, context: &Context, date: NaiveDate, texts: DetailTexts,
+                         answer: Option<Result<T, data::Error>>, make: impl FnOnce(T, Size) -> LayoutItem) -> LayoutItem {
+    let viewport = renderer.viewport_size();
+
+    let matchup = Text {
+        content: texts.title.to_owned(),
+        size: 28,
+        pos: Position::new(Point::origin(), Size::new(viewport.w, DETAIL_TITLE_HEIGHT)),
+        color: context.theme.text.clone()
+    };
+
+    let content_size = Size::new(viewport.w, viewport.h.saturating_sub(DATE_HEADER_HEIGHT + DETAIL_TITLE_HEIGHT + DETAIL_HINT_HEIGHT));
+    let message = |content: String| LayoutItem::Widget(WidgetType::Text(Text {
+        content,
+        size: 24,
+        pos: Position::new(Point::origin(), content_size.clone()),
+        color: context.theme.text.clone()
+    }));
+    let content = match answer {
+        None => { message(format!("Loading {}...", texts.what)) }
+        Some(Ok(fetched)) => { make(fetched, content_size.clone()) }
+        Some(Err(err)) => { message(format!("Could not load the {}: {}", texts.what, err)) }
+    };
+
+    let hint = Text {
+        content: texts.hint.to_owned(),
+        size: 18,
+        pos: Position::new(Point::origin(), Size::new(viewport.w, DETAIL_HINT_HEIGHT)),
+        color: context.theme.text.clone()
+    };
 
-    let mut v_layout = VCenteredLayout::new(Position::new(Point::origin(), renderer.viewport_size()));
+    let mut detail_layout = VBoxLayout::new();
+    detail_layout.set_position(Position::new(Point::origin(), Size::new(viewport.w, viewport.h.saturating_sub(DATE_HEADER_HEIGHT))));
+    detail_layout.add_child(LayoutItem::Widget(WidgetType::Text(matchup)));
+    detail_layout.add_stretched_child(content);
+    detail_layout.add_child(LayoutItem::Widget(WidgetType::Text(hint)));
 
-    let mut list_pos = list_layout.position().clone();
-    list_pos.upper_left.translate(v_layout.position().center().x - (list_layout.item_size.w / 2) as i32, 0);
-    list_layout.set_position(list_pos);
+    make_page(renderer, context, date, LayoutItem::Layout(Box::new(detail_layout)))
+}
+
+/// Lays `content` out vertically centered under a header showing `date`, on top of the splash background
+fn make_dated_scene(renderer: &SDL2Renderer, context: &Context, date: NaiveDate, content: LayoutItem) -> LayoutItem {
+    let viewport = renderer.viewport_size();
 
-    v_layout.add_child(LayoutItem::Layout(Box::new(list_layout)));
+    let mut v_layout = VCenteredLayout::new(Position::new(Point::origin(), Size::new(viewport.w, viewport.h.saturating_sub(DATE_HEADER_HEIGHT))));
+    v_layout.add_child(content);
+
+    make_page(renderer, context, date, LayoutItem::Layout(Box::new(v_layout)))
+}
 
-    let mut canvas_layout = CenteredLayout::new(Position::new(Point::origin(), renderer.viewport_size()));
+/// Puts a header showing `date` above `body`, which fills the rest of the viewport, on top of the splash background
+fn make_page(renderer: &SDL2Renderer, context: &Context, date: NaiveDate, body: LayoutItem) -> LayoutItem {
+    let splash = make_splash(renderer, &context.theme);
+    let viewport = renderer.viewport_size();
+
+    let mut page_layout = VBoxLayout::new();
+    page_layout.set_position(Position::new(Point::origin(), viewport.clone()));
+    page_layout.add_child(LayoutItem::Widget(WidgetType::Text(make_date_header(date, viewport.w, &context.theme))));
+    page_layout.add_stretched_child(body);
+
+    let mut canvas_layout = StackLayout::new(Position::new(Point::origin(), viewport));
     canvas_layout.add_child(LayoutItem::Widget(WidgetType::Image(splash)));
-    canvas_layout.add_child(LayoutItem::Layout(Box::new(v_layout)));
+    canvas_layout.add_child(LayoutItem::Layout(Box::new(page_layout)));
 
     LayoutItem::Layout(Box::new(canvas_layout))
 }
 
-struct SDL2Renderer {
+// Textures of images that were not drawn for this long are freed
+const TEXTURE_KEEP: Duration = Duration::from_secs(10);
+
+struct CachedTexture {
+    /// `None` for sources that could not be decoded, so they are not tried again every frame
+    texture: Option<Texture>,
+    last_used: Instant
+}
+
+/// Everything a rendered text depends on, in physical pixels
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct TextKey {
+    content: String,
+    point_size: u16,
+    color: (u8, u8, u8),
+    wrap_w: u32
+}
+
+// Every text is set in this face
+const TEXT_FACE: &str = "League Gothic";
+const TEXT_FACE_BYTES: &[u8] = include_bytes!("../fonts/LeagueGothic-Regular.otf");
+
+struct SDL2Renderer<'ttf> {
     context: SDLContext,
+    font_context: &'ttf Sdl2TtfContext,
     canvas: sdl2::render::WindowCanvas,
-    coord_reference: Point
+    coord_reference: Point,
+    textures: HashMap<u64, CachedTexture>,
+    text_textures: HashMap<TextKey, CachedTexture>,
+    fonts: HashMap<(&'static str, u16), Font<'ttf, 'static>>
 }
 
-impl SDL2Renderer {
-    fn new() -> Self {
+impl<'ttf> SDL2Renderer<'ttf> {
+    fn new(font_context: &'ttf Sdl2TtfContext, (window_w, window_h): (u32, u32), position: Option<(i32, i32)>, fullscreen: bool) -> Self {
         let context = SDLContext::new();
 
+        // Windows scales the window by the display's scale factor only for applications declaring they handle it
+        sdl2::hint::set("SDL_WINDOWS_DPI_AWARENESS", "permonitorv2");
+        sdl2::hint::set("SDL_WINDOWS_DPI_SCALING", "1");
         let video_subsystem = context.sdl_context.video().unwrap();
-        let window = video_subsystem.window("MLB Game Viewer", 960, 540)
-            .position_centered()
-            .build()
-            .unwrap();
+        let mut window_builder = video_subsystem.window("MLB Game Viewer", window_w, window_h);
+        window_builder.resizable().allow_highdpi();
+        match position {
+            Some((x, y)) => { window_builder.position(x, y); }
+            None => { window_builder.position_centered(); }
+        }
+        if fullscreen {
+            window_builder.fullscreen_desktop();
+        }
+        let mut window = window_builder.build().unwrap();
+        if let Err(err) = window.set_minimum_size(MIN_WINDOW_SIZE.0, MIN_WINDOW_SIZE.1) {
+            log::warn!("Could not limit how small the window gets: {}", err);
+        }
         let canvas = window.into_canvas().build().unwrap();
 
         SDL2Renderer {
             context,
+            font_context,
             canvas,
-            coord_reference: Point::origin()
+            coord_reference: Point::origin(),
+            textures: HashMap::new(),
+            text_textures: HashMap::new(),
+            fonts: HashMap::new()
+        }
+    }
+
+    fn is_fullscreen(&self) -> bool {
+        self.canvas.window().fullscreen_state() != FullscreenType::Off
+    }
+
+    /// Switches between the desktop covering fullscreen mode and a window
+    fn toggle_fullscreen(&mut self) {
+        let state = if self.is_fullscreen() { FullscreenType::Off } else { FullscreenType::Desktop };
+        if let Err(err) = self.canvas.window_mut().set_fullscreen(state) {
+            log::warn!("Could not switch fullscreen mode: {}", err);
         }
     }
 
+    /// The window's size and position on the screen, as they are to be restored next time
+    fn window_geometry(&self) -> ((u32, u32), (i32, i32)) {
+        (self.canvas.window().size(), self.canvas.window().position())
+    }
+
+    /// In logical pixels, the size scenes are laid out in
     fn viewport_size(&self) -> Size {
-        Size::new(self.canvas.viewport().width(), self.canvas.viewport().height())
+        let (w, h) = self.canvas.window().size();
+        Size::new(w, h)
+    }
+
+    /// Physical pixels drawn per logical window pixel
+    fn pixel_ratio(&self) -> f32 {
+        let (window_w, _) = self.canvas.window().size();
+        let (drawable_w, _) = self.canvas.window().drawable_size();
+        if window_w == 0 { 1.0 } else { drawable_w as f32 / window_w as f32 }
+    }
+
+    /// Where a position of the element being drawn, in logical pixels relative to its layout, lands on the canvas
+    fn to_physical(&self, position: &Position) -> Rect {
+        let ratio = self.pixel_ratio();
+        let scale = |value: i32| (value as f32 * ratio).round() as i32;
+        let Point { x, y } = translate_to_global(&position.upper_left, &self.coord_reference);
+        Rect::new(scale(x), scale(y), scale(position.size.w as i32) as u32, scale(position.size.h as i32) as u32)
+    }
+
+    /// Starts a new frame, nothing of the previous one is kept
+    fn clear(&mut self) {
+        self.canvas.set_draw_color(sdl2::pixels::Color::RGB(0, 0, 0));
+        self.canvas.clear();
     }
 
     fn present(&mut self) {
         self.canvas.present();
+        self.evict_textures();
+    }
+
+    fn evict_textures(&mut self) {
+        evict_stale(&mut self.textures);
+        evict_stale(&mut self.text_textures);
+    }
+
+    /// Renders the text once, `None` if there is nothing to show such as for empty content
+    fn render_text(&mut self, key: &TextKey) -> Option<Texture> {
+        let font_context = self.font_context;
+        let font = self.fonts.entry((TEXT_FACE, key.point_size)).or_insert_with(|| {
+            font_context.load_font_from_rwops(RWops::from_bytes(TEXT_FACE_BYTES).unwrap(), key.point_size).unwrap()
+        });
+        let (r, g, b) = key.color;
+        let surface = font.render(key.content.as_str()).blended_wrapped(sdl2::pixels::Color::RGB(r, g, b), key.wrap_w).ok()?;
+        surface.as_texture(&self.canvas.texture_creator()).ok()
+    }
+}
+
+impl Drop for SDL2Renderer<'_> {
+    // Runs before the canvas is dropped, which the textures have to go ahead of
+    fn drop(&mut self) {
+        let cached = self.textures.drain().map(|(_, cached)| cached).chain(self.text_textures.drain().map(|(_, cached)| cached));
+        for CachedTexture { texture, .. } in cached {
+            if let Some(texture) = texture {
+                unsafe { texture.destroy() }
+            }
+        }
+    }
+}
+
+/// Frees the textures that were not drawn for a while
+fn evict_stale<K: Clone + Eq + Hash>(textures: &mut HashMap<K, CachedTexture>) {
+    let now = Instant::now();
+    let stale: Vec<K> = textures.iter()
+        .filter(|(_, cached)| now.duration_since(cached.last_used) > TEXTURE_KEEP)
+        .map(|(key, _)| key.clone())
+        .collect();
+    for key in stale {
+        if let Some(CachedTexture { texture: Some(texture), .. }) = textures.remove(&key) {
+            // Textures are only freed explicitly
+            unsafe { texture.destroy() }
+        }
     }
 }
 
 struct SDLContext {
     sdl_context: sdl2::Sdl,
-    font_context: sdl2::ttf::Sdl2TtfContext,
     image_context: sdl2::image::Sdl2ImageContext
 }
 
 impl SDLContext {
     fn new() -> Self {
         let sdl_context = sdl2::init().unwrap();
-        let font_context = sdl2::ttf::init().unwrap();
         let image_context = sdl2::image::init(InitFlag::PNG | InitFlag::JPG).unwrap();
 
         SDLContext {
             sdl_context,
-            font_context,
             image_context
         }
     }
 }
 
 struct SDL2EventPropagator {
-    event: sdl2::event::Event
+    event: sdl2::event::Event,
+    consumed: bool
 }
 
 impl SDL2EventPropagator {
     pub fn new(event: Event) -> Self {
-        SDL2EventPropagator { event }
+        SDL2EventPropagator { event, consumed: false }
     }
 }
 
@@ -166,11 +1063,17 @@ impl Visitor<LayoutItem> for SDL2EventPropagator {
 
 impl Visitor<Box<dyn Layout>> for SDL2EventPropagator {
     fn visit_element(&mut self, element: &mut Box<dyn Layout>) {
-        let consumed = match self.event {
+        if self.consumed { return; }
+
+        self.consumed = match self.event {
             Event::KeyDown { keycode: Some(code), .. } => {
                 if let Some(key) = match code {
                     Keycode::Right => Some(Key::Right),
                     Keycode::Left => Some(Key::Left),
+                    Keycode::Up => Some(Key::Up),
+                    Keycode::Down => Some(Key::Down),
+                    Keycode::PageUp => Some(Key::PageUp),
+                    Keycode::PageDown => Some(Key::PageDown),
                     _ => { None }
                 } {
                     element.handle_key(key)
@@ -179,7 +1082,7 @@ impl Visitor<Box<dyn Layout>> for SDL2EventPropagator {
             _ => { false }
         };
 
-        if ! consumed {
+        if ! self.consumed {
             let mut i = 0;
             while let Some(child) = element.child_at(i) {
                 self.visit_element(child);
@@ -193,65 +1096,77 @@ impl Visitor<WidgetType> for SDL2EventPropagator {
     fn visit_element(&mut self, _: &mut WidgetType) { }
 }
 
-impl Visitor<Frame> for SDL2Renderer {
+impl Visitor<Frame> for SDL2Renderer<'_> {
     fn visit_element(&mut self, element: &mut Frame) {
         if let Some(color) = &element.color() {
             self.canvas.set_draw_color(sdl2::pixels::Color::RGB(color.r as u8, color.g as u8, color.b as u8));
         }
-        let Point { x, y } = translate_to_global(&element.position().upper_left, &self.coord_reference);
-        // println!("Drawing frame at {}, {}", x, y);
-        self.canvas.fill_rect(sdl2::rect::Rect::new(x as i32, y as i32, element.position().size.w, element.position().size.h)).unwrap();
+        let rect = self.to_physical(element.position());
+        if element.outline {
+            self.canvas.draw_rect(rect).unwrap();
+            // A second pass inside the first so the outline stands out on the background
+            if rect.width() > 2 && rect.height() > 2 {
+                self.canvas.draw_rect(Rect::new(rect.x() + 1, rect.y() + 1, rect.width() - 2, rect.height() - 2)).unwrap();
+            }
+        } else {
+            self.canvas.fill_rect(rect).unwrap();
+        }
     }
 }
 
-impl Visitor<Image> for SDL2Renderer {
+impl Visitor<Image> for SDL2Renderer<'_> {
     fn visit_element(&mut self, element: &mut Image) {
-        let creator = self.canvas.texture_creator();
-
-        let texture = match &element.source {
-            ImageSource::Path(path) => {
-                match creator.load_texture(path) {
-                    Ok(texture) => { Some(texture) }
-                    Err(_) => { None }
-                }
-            }
-            ImageSource::Bytes(bytes) => {
-                match creator.load_texture_bytes(bytes.as_slice()) {
-                    Ok(texture) => { Some(texture) }
-                    Err(_) => { None }
-                }
-            }
-        };
-        let mut dest_pos = element.position().clone();
-        dest_pos.upper_left = translate_to_global(&dest_pos.upper_left, &self.coord_reference);
-        let Position {upper_left: Point {x, y}, size: Size { w, h }} = dest_pos;
-        match texture {
+        let dest_rect = self.to_physical(element.position());
+        let canvas = &mut self.canvas;
+        // Decoded the first time the source is drawn, later frames reuse the texture
+        let cached = self.textures.entry(element.key()).or_insert_with(|| {
+            let creator = canvas.texture_creator();
+            let texture = match element.source() {
+                ImageSource::Path(path) => { creator.load_texture(path).ok() }
+                ImageSource::Bytes(bytes) => { creator.load_texture_bytes(bytes.as_slice()).ok() }
+            };
+            CachedTexture { texture, last_used: Instant::now() }
+        });
+        cached.last_used = Instant::now();
+        match &cached.texture {
             None => {
-                self.canvas.set_draw_color(sdl2::pixels::Color::GRAY);
-                self.canvas.fill_rect(Rect::new(x as i32, y as i32, w, h)).unwrap();
+                canvas.set_draw_color(sdl2::pixels::Color::GRAY);
+                canvas.fill_rect(dest_rect).unwrap();
             }
             Some(t) => {
-                self.canvas.copy(&t, None, Rect::new(x as i32, y as i32, w, h)).unwrap();
+                canvas.copy(t, None, dest_rect).unwrap();
             }
         }
     }
 }
 
-impl Visitor<Text> for SDL2Renderer {
+impl Visitor<Text> for SDL2Renderer<'_> {
     fn visit_element(&mut self, element: &mut Text) {
         let Color { r, g, b} = element.color;
-        let font_bytes = include_bytes!("../fonts/LeagueGothic-Regular.otf");
-        let font = self.context.font_context.load_font_from_rwops(RWops::from_bytes(font_bytes).unwrap(), element.size as u16).unwrap();
-        if let Ok(font_surface) = font.render(element.content.as_str()).blended_wrapped(sdl2::pixels::Color::RGB(r as u8, g as u8, b as u8), element.position().size.w) {
-            let (rendered_w, rendered_h) = font_surface.size();
-            let translated_center = translate_to_global(&element.position().center(), &self.coord_reference);
-            let dest_rect = Rect::new(translated_center.x - (rendered_w / 2) as i32, translated_center.y - (rendered_h / 2) as i32, rendered_w, rendered_h);
-            self.canvas.copy(&font_surface.as_texture(&self.canvas.texture_creator()).unwrap(), None, dest_rect).unwrap();
+        // Point sizes are logical like the layout, the glyphs are rendered at the display's resolution
+        let ratio = self.pixel_ratio();
+        let key = TextKey {
+            content: element.content.clone(),
+            point_size: (element.size as f32 * ratio).round() as u16,
+            color: (r as u8, g as u8, b as u8),
+            wrap_w: (element.position().size.w as f32 * ratio).round() as u32
+        };
+        let area = self.to_physical(element.position());
+
+        if !self.text_textures.contains_key(&key) {
+            let texture = self.render_text(&key);
+            self.text_textures.insert(key.clone(), CachedTexture { texture, last_used: Instant::now() });
+        }
+        let cached = self.text_textures.get_mut(&key).unwrap();
+        cached.last_used = Instant::now();
+        if let Some(texture) = &cached.texture {
+            let query = texture.query();
+            self.canvas.copy(texture, None, Rect::from_center(area.center(), query.width, query.height)).unwrap();
         }
     }
 }
 
-impl Visitor<LayoutItem> for SDL2Renderer {
+impl Visitor<LayoutItem> for SDL2Renderer<'_> {
     fn visit_element(&mut self, element: &mut LayoutItem) {
         match element {
             LayoutItem::Layout(l) => { self.visit_element(l) }
@@ -260,7 +1175,7 @@ impl Visitor<LayoutItem> for SDL2Renderer {
     }
 }
 
-impl Visitor<Box<dyn Layout>> for SDL2Renderer {
+impl Visitor<Box<dyn Layout>> for SDL2Renderer<'_> {
     fn visit_element(&mut self, element: &mut Box<dyn Layout>) {
         let mut i = 0;
         self.coord_reference = translate_to_global(&element.position().upper_left, &self.coord_reference);
@@ -270,10 +1185,11 @@ impl Visitor<Box<dyn Layout>> for SDL2Renderer {
             i += 1;
         }
         self.coord_reference = translate_to_relative(&self.coord_reference, &element.position().upper_left);
+        element.mark_clean();
     }
 }
 
-impl Visitor<WidgetType> for SDL2Renderer {
+impl Visitor<WidgetType> for SDL2Renderer<'_> {
     fn visit_element(&mut self, element: &mut WidgetType) {
         match element {
             WidgetType::Frame(frame) => { self.visit_element(frame) }
@@ -0,0 +1,37 @@
+use std::io;
+use std::process::{Command, Stdio};
+use std::thread;
+
+/// Opens urls in the platform's default handler for them
+#[cfg(target_os = "windows")]
+pub const DEFAULT_PLAYER: &str = "explorer";
+#[cfg(target_os = "macos")]
+pub const DEFAULT_PLAYER: &str = "open";
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub const DEFAULT_PLAYER: &str = "xdg-open";
+
+/// A video player run as its own process, such as "mpv --fs", with the url appended as the last argument
+pub struct ExternalPlayer {
+    program: String,
+    args: Vec<String>
+}
+
+impl ExternalPlayer {
+    pub fn new(command: &str) -> Option<Self> {
+        let mut words = command.split_whitespace().map(|word| word.to_owned());
+        let program = words.next()?;
+        Some(ExternalPlayer { program, args: words.collect() })
+    }
+
+    /// Starts playing `url` without waiting for the player to exit
+    pub fn play(&self, url: &str) -> io::Result<()> {
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .arg(url)
+            .stdin(Stdio::null())
+            .spawn()?;
+        // Reap the player once it exits so it does not linger as a zombie
+        thread::spawn(move || child.wait());
+        Ok(())
+    }
+}
@@ -0,0 +1,455 @@
+//! Typed views of the statsapi responses, only covering the fields the viewer uses
+
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Deserialize, Debug)]
+pub struct ScheduleResponse {
+    pub dates: Vec<ScheduleDate>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ScheduleDate {
+    #[serde(default)]
+    pub games: Vec<Game>
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Game {
+    pub game_pk: u64,
+    // UTC, as in 2020-09-01T17:10:00Z
+    pub game_date: String,
+    // As in "2020", left out by some older responses
+    pub season: Option<String>,
+    pub status: GameStatus,
+    pub teams: GameTeams,
+    pub linescore: Option<GameLinescore>,
+    pub content: Option<Content>,
+    pub decisions: Option<Decisions>
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GameStatus {
+    pub abstract_game_state: String,
+    #[serde(default)]
+    pub coded_game_state: String,
+    pub detailed_state: String
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct GameLinescore {
+    pub current_inning: Option<u32>,
+    pub current_inning_ordinal: Option<String>,
+    pub inning_half: Option<String>,
+    #[serde(default)]
+    pub innings: Vec<LinescoreInning>,
+    pub teams: Option<LinescoreTeams>
+}
+
+// Innings are listed in the order they were played
+#[derive(Deserialize, Debug)]
+pub struct LinescoreInning {
+    pub home: LinescoreLine,
+    pub away: LinescoreLine
+}
+
+#[derive(Deserialize, Debug)]
+pub struct LinescoreTeams {
+    pub home: LinescoreLine,
+    pub away: LinescoreLine
+}
+
+/// A team's line for one inning or, under `teams`, for the whole game.
+/// Innings the team did not bat in have no runs.
+#[derive(Deserialize, Debug)]
+pub struct LinescoreLine {
+    pub runs: Option<u32>,
+    pub hits: Option<u32>,
+    pub errors: Option<u32>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GameTeams {
+    pub home: GameTeam,
+    pub away: GameTeam
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GameTeam {
+    pub team: Team,
+    pub score: Option<u32>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Team {
+    pub id: u64,
+    pub name: String
+}
+
+/// Only present once a game is final
+#[derive(Deserialize, Debug)]
+pub struct Decisions {
+    pub winner: Option<Person>,
+    pub loser: Option<Person>,
+    pub save: Option<Person>
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Person {
+    pub full_name: String,
+    // Only filled in when the person is hydrated with their stats
+    #[serde(default)]
+    pub stats: Vec<PersonStats>
+}
+
+/// One group of stats, split by season for the `season` stat type
+#[derive(Deserialize, Debug)]
+pub struct PersonStats {
+    pub group: Option<StatGroup>,
+    #[serde(default)]
+    pub splits: Vec<StatSplit>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct StatSplit {
+    pub season: Option<String>,
+    #[serde(default)]
+    pub stat: PitchingStats
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StatGroup {
+    pub display_name: String
+}
+
+#[derive(Deserialize, Debug, Default)]
+pub struct PitchingStats {
+    pub wins: Option<u32>,
+    pub losses: Option<u32>,
+    pub saves: Option<u32>,
+    // A string like "3.25", which may also be "-.--" before the first out
+    pub era: Option<String>
+}
+
+impl Game {
+    /// Falls back to the year of the game for responses without the season
+    pub fn season(&self) -> &str {
+        self.season.as_deref().unwrap_or_else(|| self.game_date.get(..4).unwrap_or_default())
+    }
+}
+
+impl Person {
+    /// The pitching stats of the given season, not those of the current one the api may answer with
+    pub fn pitching_stats(&self, season: &str) -> Option<&PitchingStats> {
+        self.stats.iter()
+            .filter(|stats| stats.group.as_ref().is_some_and(|group| group.display_name == "pitching"))
+            .flat_map(|stats| stats.splits.iter())
+            .find(|split| split.season.as_deref() == Some(season))
+            .map(|split| &split.stat)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Content {
+    pub editorial: Option<Editorial>,
+    pub highlights: Option<ContentHighlights>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ContentHighlights {
+    pub highlights: Option<HighlightList>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct HighlightList {
+    #[serde(default)]
+    pub items: Vec<MediaItem>
+}
+
+/// A highlight video
+#[derive(Deserialize, Debug)]
+pub struct MediaItem {
+    pub title: Option<String>,
+    // As in "00:01:38"
+    pub duration: Option<String>,
+    pub image: Option<ArticleImage>,
+    #[serde(default)]
+    pub playbacks: Vec<Playback>
+}
+
+/// One encoding of a video, named like "mp4Avc", "highBit" or "HTTP_CLOUD_WIRED_60"
+#[derive(Deserialize, Debug)]
+pub struct Playback {
+    pub name: String,
+    pub url: String
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Editorial {
+    pub recap: Option<Recap>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Recap {
+    pub mlb: Option<Article>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Article {
+    pub headline: Option<String>,
+    pub image: Option<ArticleImage>,
+    // The recap video
+    pub media: Option<MediaItem>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ArticleImage {
+    #[serde(default)]
+    pub cuts: Vec<ImageCut>
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ImageCut {
+    pub width: u32,
+    pub height: u32,
+    pub src: String
+}
+
+impl Game {
+    /// The recap article, which only exists once a game has been written up
+    pub fn recap(&self) -> Option<&Article> {
+        self.content.as_ref()
+            .and_then(|content| content.editorial.as_ref())
+            .and_then(|editorial| editorial.recap.as_ref())
+            .and_then(|recap| recap.mlb.as_ref())
+    }
+
+    /// The recap video first, then the other highlights
+    pub fn highlights(&self) -> Vec<&MediaItem> {
+        let recap = self.recap().and_then(|recap| recap.media.as_ref());
+        let others = self.content.as_ref()
+            .and_then(|content| content.highlights.as_ref())
+            .and_then(|highlights| highlights.highlights.as_ref())
+            .map(|highlights| highlights.items.iter())
+            .into_iter()
+            .flatten();
+        recap.into_iter().chain(others).collect()
+    }
+
+    pub fn is_final(&self) -> bool {
+        self.status.abstract_game_state == "Final"
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BoxscoreResponse {
+    pub teams: BoxscoreTeams
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BoxscoreTeams {
+    pub home: BoxscoreTeam,
+    pub away: BoxscoreTeam
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BoxscoreTeam {
+    pub team: Team,
+    // Keyed by "ID" followed by the person id
+    #[serde(default)]
+    pub players: HashMap<String, BoxscorePlayer>,
+    // Person ids in batting order, substitutes following the player they replaced
+    #[serde(default)]
+    pub batters: Vec<u64>,
+    // Person ids in the order they pitched
+    #[serde(default)]
+    pub pitchers: Vec<u64>
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BoxscorePlayer {
+    pub person: Person,
+    pub position: Option<PlayerPosition>,
+    #[serde(default)]
+    pub stats: PlayerStats,
+    #[serde(default)]
+    pub season_stats: PlayerStats
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PlayerPosition {
+    pub abbreviation: String
+}
+
+/// Players who did not bat or pitch have empty objects for the respective group
+#[derive(Deserialize, Debug, Default)]
+pub struct PlayerStats {
+    #[serde(default)]
+    pub batting: BattingStats,
+    #[serde(default)]
+    pub pitching: BoxscorePitchingStats
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BattingStats {
+    pub at_bats: Option<u32>,
+    pub runs: Option<u32>,
+    pub hits: Option<u32>,
+    pub rbi: Option<u32>,
+    pub base_on_balls: Option<u32>,
+    pub strike_outs: Option<u32>,
+    // Only in the season stats, as in ".275"
+    pub avg: Option<String>
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BoxscorePitchingStats {
+    // Outs are counted in thirds, as in "5.2"
+    pub innings_pitched: Option<String>,
+    pub hits: Option<u32>,
+    pub runs: Option<u32>,
+    pub earned_runs: Option<u32>,
+    pub base_on_balls: Option<u32>,
+    pub strike_outs: Option<u32>,
+    // Only in the season stats
+    pub era: Option<String>
+}
+
+impl BoxscoreTeam {
+    pub fn player(&self, id: u64) -> Option<&BoxscorePlayer> {
+        self.players.get(&format!("ID{}", id))
+    }
+}
+
+/// The live game feed, of which only the plays are used
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveFeedResponse {
+    pub live_data: LiveData
+}
+
+#[derive(Deserialize, Debug)]
+pub struct LiveData {
+    pub plays: Plays
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Plays {
+    // In the order they happened, the last one may still be in progress
+    #[serde(default)]
+    pub all_plays: Vec<Play>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Play {
+    pub result: PlayResult,
+    pub about: PlayAbout,
+    pub matchup: PlayMatchup
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayResult {
+    pub description: Option<String>,
+    pub away_score: Option<u32>,
+    pub home_score: Option<u32>
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayAbout {
+    pub inning: u32,
+    // "top" or "bottom"
+    pub half_inning: String,
+    #[serde(default)]
+    pub is_scoring_play: bool
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PlayMatchup {
+    pub batter: Person,
+    pub pitcher: Person
+}
+
+#[derive(Deserialize, Debug)]
+pub struct LeaguesResponse {
+    #[serde(default)]
+    pub leagues: Vec<League>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct League {
+    pub id: u64
+}
+
+#[derive(Deserialize, Debug)]
+pub struct StandingsResponse {
+    #[serde(default)]
+    pub records: Vec<StandingsRecord>
+}
+
+/// The standings of one division
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StandingsRecord {
+    // Only named when hydrated with the division
+    pub division: Option<Division>,
+    #[serde(default)]
+    pub team_records: Vec<TeamRecord>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Division {
+    pub name: Option<String>
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamRecord {
+    pub team: Team,
+    pub wins: u32,
+    pub losses: u32,
+    // As in ".600"
+    pub winning_percentage: String,
+    // "-" for the division leader
+    pub games_back: String,
+    pub streak: Option<Streak>,
+    pub records: Option<SplitRecords>
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Streak {
+    // As in "W3"
+    pub streak_code: String
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SplitRecords {
+    #[serde(default)]
+    pub split_records: Vec<SplitRecord>
+}
+
+#[derive(Deserialize, Debug)]
+pub struct SplitRecord {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub wins: u32,
+    pub losses: u32
+}
+
+impl TeamRecord {
+    pub fn last_ten(&self) -> Option<&SplitRecord> {
+        self.records.as_ref()?.split_records.iter().find(|record| record.kind == "lastTen")
+    }
+}
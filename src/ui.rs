@@ -1,11 +1,18 @@
 use std::path::{PathBuf};
 
+use crate::util::fnv1a;
+
 #[derive(Debug)]
 pub enum Key {
     Right,
-    Left
+    Left,
+    Up,
+    Down,
+    PageUp,
+    PageDown
 }
 
+/// In logical pixels, which the renderer scales to the display's physical pixels when drawing
 #[derive(Debug)]
 pub(crate) struct Size {
     pub(crate) w: u32,
@@ -16,6 +23,11 @@ impl Size {
     pub fn new(w: u32, h: u32) -> Self {
         Size { w, h }
     }
+
+    /// The width and height, as the data layer takes them
+    pub(crate) fn dimensions(&self) -> (u32, u32) {
+        (self.w, self.h)
+    }
 }
 
 impl Clone for Size {
@@ -25,6 +37,7 @@ impl Clone for Size {
     }
 }
 
+/// In logical pixels, like `Size`
 #[derive(Debug)]
 pub(crate) struct Point {
     pub x: i32,
@@ -39,11 +52,6 @@ impl Point {
     pub(crate) fn origin() -> Self {
         Point { x: 0, y: 0 }
     }
-
-    pub(crate) fn translate(&mut self, x: i32, y: i32) {
-        self.x += x;
-        self.y += y;
-    }
 }
 
 pub struct Color {
@@ -58,6 +66,36 @@ impl Color {
     }
 }
 
+impl Clone for Color {
+    fn clone(&self) -> Self {
+        let Color { r, g, b } = *self;
+        Color { r, g, b }
+    }
+}
+
+/// The background and colors every scene is drawn with
+pub struct Theme {
+    pub(crate) background: PathBuf,
+    pub(crate) text: Color,
+    pub(crate) muted_text: Color,
+    /// Behind the cards listing highlights and plays and the game's score strip
+    pub(crate) panel: Color,
+    /// Frames the games of favorite teams
+    pub(crate) favorite: Color
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            background: PathBuf::from("./images/background.png"),
+            text: Color::new(255, 255, 255),
+            muted_text: Color::new(180, 180, 180),
+            panel: Color::new(20, 30, 60),
+            favorite: Color::new(255, 200, 40)
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Position {
     pub(crate) upper_left: Point,
@@ -88,7 +126,9 @@ impl Clone for Position {
 }
 
 pub struct Image {
-    pub(crate) source: ImageSource,
+    source: ImageSource,
+    // Derived from the source, so the renderer can keep its texture for as long as the source stays the same
+    key: u64,
     pos: Position,
     preserve_aspect_w: bool
 }
@@ -98,26 +138,58 @@ pub enum ImageSource {
     Bytes(Vec<u8>)
 }
 
+impl ImageSource {
+    fn key(&self) -> u64 {
+        match self {
+            ImageSource::Path(path) => { fnv1a(format!("path:{}", path.display()).as_bytes()) }
+            ImageSource::Bytes(bytes) => { fnv1a(bytes) }
+        }
+    }
+}
+
 impl Image {
     pub fn new(source: PathBuf, pos: Position) -> Self {
-        Image {
-            source: ImageSource::Path(source),
-            pos,
-            preserve_aspect_w: true
-        }
+        Image::with_source(ImageSource::Path(source), pos)
     }
 
     pub fn from_bytes(data: Vec<u8>, pos: Position) -> Self {
+        Image::with_source(ImageSource::Bytes(data), pos)
+    }
+
+    fn with_source(source: ImageSource, pos: Position) -> Self {
         Image {
-            source: ImageSource::Bytes(data),
+            key: source.key(),
+            source,
             pos,
             preserve_aspect_w: true
         }
     }
+
+    pub(crate) fn source(&self) -> &ImageSource {
+        &self.source
+    }
+
+    /// Equal for images showing the same file or the same bytes
+    pub(crate) fn key(&self) -> u64 {
+        self.key
+    }
+
+    /// Shows `data` instead, in the same place
+    pub(crate) fn set_bytes(&mut self, data: Vec<u8>) {
+        self.source = ImageSource::Bytes(data);
+        self.key = self.source.key();
+    }
+
+    /// The size an image preserving its aspect takes when laid out `w` wide
+    pub(crate) fn size_for_width(w: u32) -> Size {
+        let aspect = 16.0 / 9.0;
+        Size::new(w, (w as f32 / aspect) as u32)
+    }
 }
 
 pub struct Text {
     pub content: String,
+    /// The point size at a pixel ratio of 1
     pub size: u32,
     pub pos: Position,
     pub color: Color
@@ -126,20 +198,40 @@ pub struct Text {
 pub struct Frame {
     pos: Position,
     fill: Option<Color>,
+    // Only the border is drawn in the fill color
+    pub(crate) outline: bool
 }
 
 impl Frame {
     fn new(position: Position) -> Self {
         Frame {
             pos: position,
-            fill: None
+            fill: None,
+            outline: false
         }
     }
 
     fn empty() -> Self {
         Frame {
             pos: Position { upper_left: Point::origin(), size: Size { w: 0, h: 0 } },
-            fill: None
+            fill: None,
+            outline: false
+        }
+    }
+
+    fn filled(position: Position, color: Color) -> Self {
+        Frame {
+            pos: position,
+            fill: Some(color),
+            outline: false
+        }
+    }
+
+    fn outlined(position: Position, color: Color) -> Self {
+        Frame {
+            pos: position,
+            fill: Some(color),
+            outline: true
         }
     }
 
@@ -153,7 +245,8 @@ pub(crate) struct ListLayout {
     position: Position,
     pub(crate) item_size: Size,
     spacing: u32,
-    selected: usize
+    selected: usize,
+    dirty: bool
 }
 
 impl ListLayout {
@@ -166,134 +259,547 @@ impl ListLayout {
             },
             item_size,
             spacing: 20,
-            selected: 0
+            selected: 0,
+            dirty: true
         }
     }
 
     pub fn add_item(&mut self, mut item: ListItem) {
         item.set_position(Position {
             upper_left: Point {
-                x: self.position.size.w as i32 + self.spacing as i32,
+                x: (self.children.len() as u32 * (self.spacing + self.item_size.w) + self.spacing) as i32,
                 y: 0
             },
             size: self.item_size.clone()
         });
-        self.position.size.w += self.spacing + self.item_size.w;
         self.children.push(item);
+        self.dirty = true;
     }
 
     fn select_next(&mut self) {
-        if self.selected < self.children.len() - 1 {
+        if self.selected + 1 < self.children.len() {
             self.selected += 1;
-            self.position.upper_left.translate(0 - (self.item_size.w + self.spacing) as i32, 0)
+            self.center_selected();
         }
     }
 
     fn select_prev(&mut self) {
         if self.selected > 0 {
             self.selected -= 1;
-            self.position.upper_left.translate((self.item_size.w + self.spacing) as i32, 0)
+            self.center_selected();
+        }
+    }
+
+    // The list is as wide as the space it is shown in and scrolls so that the selected item sits in its middle
+    fn center_selected(&mut self) {
+        let step = (self.item_size.w + self.spacing) as i32;
+        self.position.upper_left.x = (self.position.size.w / 2) as i32 - (self.item_size.w / 2) as i32 - self.selected as i32 * step;
+        self.dirty = true;
+    }
+}
+
+/// The vertical counterpart of `ListLayout`, showing the items that fit its height
+/// and scrolling to keep the selected one in view
+pub(crate) struct VListLayout {
+    children: Vec<ListItem>,
+    position: Position,
+    item_size: Size,
+    spacing: u32,
+    selected: usize,
+    first: usize,
+    dirty: bool
+}
+
+impl VListLayout {
+    pub(crate) fn new(item_size: Size, height: u32) -> Self {
+        VListLayout {
+            children: vec![],
+            position: Position::new(Point::origin(), Size::new(item_size.w, height)),
+            item_size,
+            spacing: 10,
+            selected: 0,
+            first: 0,
+            dirty: true
+        }
+    }
+
+    pub fn add_item(&mut self, mut item: ListItem) {
+        // Rows are told apart by the selected item's look rather than by shrinking the others
+        item.scale = 1.0;
+        self.children.push(item);
+        self.position_children();
+    }
+
+    fn visible_count(&self) -> usize {
+        ((self.position.size.h + self.spacing) / (self.item_size.h + self.spacing)).max(1) as usize
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = index;
+        let visible = self.visible_count();
+        // Growing taller shows more of the items above rather than leaving room below the last one
+        self.first = self.first.min(self.children.len().saturating_sub(visible));
+        if self.selected < self.first {
+            self.first = self.selected;
+        } else if self.selected >= self.first + visible {
+            self.first = self.selected + 1 - visible;
+        }
+        self.position_children();
+    }
+
+    fn position_children(&mut self) {
+        let x = (self.position.size.w.saturating_sub(self.item_size.w) / 2) as i32;
+        let step = (self.item_size.h + self.spacing) as i32;
+        for (index, child) in self.children.iter_mut().enumerate() {
+            child.set_position(Position::new(Point::new(x, (index as i32 - self.first as i32) * step), self.item_size.clone()));
         }
+        self.dirty = true;
     }
 }
 
+// Unselected list items are shrunk by this factor around their center
+const UNSELECTED_SCALE: f32 = 0.75;
+
 pub struct ListItem {
+    key: u64,
     item: LayoutItem,
     selected_item: LayoutItem,
-    pos: Position
+    pos: Position,
+    // The unselected item is shrunk by this factor around its center
+    scale: f32
 }
 
 impl ListItem {
-    fn new(unselected: LayoutItem, selected: LayoutItem) -> Self {
+    fn new(key: u64, unselected: LayoutItem, selected: LayoutItem) -> Self {
         ListItem {
+            key,
             item: unselected,
             selected_item: selected,
-            pos: Position::new(Point::origin(), Size::new(0, 0))
+            pos: Position::new(Point::origin(), Size::new(0, 0)),
+            scale: UNSELECTED_SCALE
         }
     }
+
+    /// Shows new image data in the unselected and in the selected look, keeping everything else
+    fn set_images(&mut self, unselected: Vec<u8>, selected: Vec<u8>) {
+        self.item.accept_visitor(&mut ImageSetter { data: unselected });
+        self.selected_item.accept_visitor(&mut ImageSetter { data: selected });
+    }
+
+    /// Puts `item` where this item is, at the same place and scale
+    fn replace_with(&mut self, mut item: ListItem) {
+        item.scale = self.scale;
+        item.set_position(self.pos.clone());
+        *self = item;
+    }
 }
 
 pub struct GameItemFactory;
 
 impl GameItemFactory {
-    pub(crate) fn make(model: crate::data::GameModel) -> ListItem {
-        ListItem::new(GameItemFactory::make_item(&model), GameItemFactory::make_selected_item(&model))
+    pub(crate) const ITEM_SIZE: Size = Size { w: 200, h: 300 };
+
+    /// The thumbnail sizes on the unselected and on the selected card, each one shows its own image cut
+    pub(crate) fn thumbnail_sizes() -> [Size; 2] {
+        let selected_w = GameItemFactory::ITEM_SIZE.w;
+        let unselected_w = (selected_w as f32 * UNSELECTED_SCALE) as u32;
+        [Image::size_for_width(unselected_w), Image::size_for_width(selected_w)]
     }
 
-    fn make_item(model: &crate::data::GameModel) -> LayoutItem {
+    /// Swaps the thumbnails of `model` that arrived since its card was made into the card
+    pub(crate) fn thumbnail_setter(model: &crate::data::GameModel, pixel_ratio: f32) -> ItemImageSetter {
+        let [unselected, selected] = GameItemFactory::thumbnail_sizes();
+        ItemImageSetter::new(model.game_pk, model.thumbnail(unselected.dimensions(), pixel_ratio), model.thumbnail(selected.dimensions(), pixel_ratio))
+    }
+
+    /// Frames the cards of the games of `favorites`
+    pub(crate) fn make(model: &crate::data::GameModel, favorites: &crate::data::Teams, theme: &Theme, pixel_ratio: f32) -> ListItem {
+        let item = GameItemFactory::make_item(model, pixel_ratio);
+        let selected_item = GameItemFactory::make_selected_item(model, theme, pixel_ratio);
+        if favorites.plays_in(model) {
+            let framed = |card| LayoutItem::Layout(Box::new(FramedLayout::new(card, theme.favorite.clone())));
+            ListItem::new(model.game_pk, framed(item), framed(selected_item))
+        } else {
+            ListItem::new(model.game_pk, item, selected_item)
+        }
+    }
+
+    fn make_item(model: &crate::data::GameModel, pixel_ratio: f32) -> LayoutItem {
+        let [size, _] = GameItemFactory::thumbnail_sizes();
         let thumbnail = Image::from_bytes(
-            model.image.clone(),
-            Position::new(Point::origin(),
-            Size::new(model.image_w, model.image_h)));
+            model.thumbnail(size.dimensions(), pixel_ratio),
+            Position::new(Point::origin(), size));
 
 
         let mut vb_layout = VBoxLayout::new();
         vb_layout.add_child(LayoutItem::Widget(WidgetType::Image(thumbnail)));
 
-        let mut vc_layout = VCenteredLayout::new(Position::new(Point::origin(), Size::new(200, 300)));
+        let mut vc_layout = VCenteredLayout::new(Position::new(Point::origin(), GameItemFactory::ITEM_SIZE));
         vc_layout.add_child(LayoutItem::Layout(Box::new(vb_layout)));
 
         LayoutItem::Layout(Box::new(vc_layout))
     }
 
-    fn make_selected_item(model: &crate::data::GameModel) -> LayoutItem {
+    fn make_selected_item(model: &crate::data::GameModel, theme: &Theme, pixel_ratio: f32) -> LayoutItem {
         let home_team = Text {
             content: model.home_team.clone(),
             size: 18,
             pos: Position { upper_left: Point::origin(), size: Size::new(0, 30) },
-            color: Color::new(255, 255, 255)
+            color: theme.text.clone()
         };
 
         let vs = Text {
             content: "VS".to_owned(),
             size: 12,
             pos: Position { upper_left: Point::origin(), size: Size::new(0, 10) },
-            color: Color::new(255, 255, 255)
+            color: theme.text.clone()
         };
 
         let away_team = Text {
             content: model.away_team.clone(),
             size: 18,
             pos: Position { upper_left: Point::origin(), size: Size::new(0, 30) },
-            color: Color::new(255, 255, 255)
+            color: theme.text.clone()
         };
 
+        let scoreboard = GameItemFactory::make_scoreboard(model, theme);
+
+        let [_, size] = GameItemFactory::thumbnail_sizes();
         let thumbnail = Image::from_bytes(
-            model.image.clone(),
-            Position::new(Point::origin(),
-                          Size::new(model.image_w, model.image_h)));
+            model.thumbnail(size.dimensions(), pixel_ratio),
+            Position::new(Point::origin(), size));
 
         let desc = Text {
             content: model.description.clone(),
             size: 12,
             pos: Position::new( Point::origin(), Size::new(0, 30)),
-            color: Color::new(255, 255, 255)
+            color: theme.text.clone()
         };
 
         let mut vb_layout = VBoxLayout::new();
         vb_layout.add_child(LayoutItem::Widget(WidgetType::Text(home_team)));
         vb_layout.add_child(LayoutItem::Widget(WidgetType::Text(vs)));
         vb_layout.add_child(LayoutItem::Widget(WidgetType::Text(away_team)));
+        vb_layout.add_child(scoreboard);
         vb_layout.add_child(LayoutItem::Widget(WidgetType::Image(thumbnail)));
         vb_layout.add_child(LayoutItem::Widget(WidgetType::Text(desc)));
+        if model.status == crate::data::GameStatus::Final {
+            for decision in GameItemFactory::make_decisions(&model.decisions, theme) {
+                vb_layout.add_child(decision);
+            }
+        }
 
-        let mut vc_layout = VCenteredLayout::new(Position::new(Point::origin(), Size::new(200, 300)));
+        let mut vc_layout = VCenteredLayout::new(Position::new(Point::origin(), GameItemFactory::ITEM_SIZE));
         vc_layout.add_child(LayoutItem::Layout(Box::new(vb_layout)));
 
         LayoutItem::Layout(Box::new(vc_layout))
     }
+
+    /// One line per pitcher credited with the win, the loss and the save, like "W: Gerrit Cole (10-4, 3.25 ERA)"
+    fn make_decisions(decisions: &crate::data::Decisions, theme: &Theme) -> Vec<LayoutItem> {
+        let lines = [("W", &decisions.winner), ("L", &decisions.loser), ("S", &decisions.save)];
+        lines.iter()
+            .filter_map(|(label, pitcher)| pitcher.as_ref().map(|pitcher| (label, pitcher)))
+            .map(|(label, pitcher)| {
+                // A save is counted in saves rather than by the record
+                let record = if *label == "S" {
+                    pitcher.saves.map(|saves| saves.to_string())
+                } else {
+                    pitcher.record()
+                };
+                let content = match record {
+                    Some(record) => { format!("{}: {} ({})", label, pitcher.name, record) }
+                    None => { format!("{}: {}", label, pitcher.name) }
+                };
+                LayoutItem::Widget(WidgetType::Text(Text {
+                    content,
+                    size: 12,
+                    pos: Position::new(Point::origin(), Size::new(0, 14)),
+                    color: theme.muted_text.clone()
+                }))
+            })
+            .collect()
+    }
+
+    /// A strip with the score and the inning or status, listing the home team first like the card does
+    fn make_scoreboard(model: &crate::data::GameModel, theme: &Theme) -> LayoutItem {
+        use crate::data::GameStatus;
+
+        let score = match (model.home_runs, model.away_runs) {
+            (Some(home), Some(away)) => { Some(format!("{} - {}", home, away)) }
+            _ => { None }
+        };
+        let state = match model.status {
+            GameStatus::Scheduled | GameStatus::PreGame => {
+                match &model.start_time {
+                    Some(start) => { start.format("%-I:%M %p").to_string() }
+                    None => { model.status.to_string() }
+                }
+            }
+            GameStatus::InProgress => {
+                match &model.inning {
+                    Some(inning) => { inning.to_string() }
+                    None => { model.status.to_string() }
+                }
+            }
+            _ => { model.status.to_string() }
+        };
+        let content = match (model.status, score) {
+            (GameStatus::Scheduled, _) | (GameStatus::Postponed, _) | (_, None) => { state }
+            (_, Some(score)) => { format!("{}   {}", score, state) }
+        };
+
+        let strip_size = Size::new(GameItemFactory::ITEM_SIZE.w, 24);
+        let background = Frame::filled(Position::new(Point::origin(), strip_size.clone()), theme.panel.clone());
+        let line = Text {
+            content,
+            size: 16,
+            pos: Position::new(Point::origin(), strip_size.clone()),
+            color: theme.text.clone()
+        };
+
+        let mut strip = CenteredLayout::new(Position::new(Point::origin(), strip_size));
+        strip.add_child(LayoutItem::Widget(WidgetType::Frame(background)));
+        strip.add_child(LayoutItem::Widget(WidgetType::Text(line)));
+
+        LayoutItem::Layout(Box::new(strip))
+    }
+}
+
+pub struct LinescoreFactory;
+
+impl LinescoreFactory {
+    const TEAM_COLUMN_W: u32 = 200;
+    const INNING_COLUMN_W: u32 = 36;
+    const TOTAL_COLUMN_W: u32 = 44;
+    const ROW_H: u32 = 36;
+    // Innings not played yet are shown empty up to a regulation game
+    const REGULATION_INNINGS: usize = 9;
+
+    /// The inning by inning grid with the run, hit and error totals, the away team batting first on top
+    pub(crate) fn make(model: &crate::data::GameModel, theme: &Theme) -> LayoutItem {
+        let linescore = &model.linescore;
+        let innings = linescore.innings().max(LinescoreFactory::REGULATION_INNINGS);
+
+        let mut column_widths = vec![LinescoreFactory::TEAM_COLUMN_W];
+        column_widths.extend(vec![LinescoreFactory::INNING_COLUMN_W; innings]);
+        column_widths.extend(vec![LinescoreFactory::TOTAL_COLUMN_W; 3]);
+        let mut grid = GridLayout::new(column_widths, LinescoreFactory::ROW_H);
+
+        let mut header = vec![String::new()];
+        header.extend((1..=innings).map(|inning| inning.to_string()));
+        header.extend(["R", "H", "E"].iter().map(|total| total.to_string()));
+        grid.add_row(LinescoreFactory::make_cells(header, theme.muted_text.clone()));
+
+        for (team, row) in [(&model.away_team, &linescore.away), (&model.home_team, &linescore.home)].iter() {
+            let show = |value: Option<u32>| value.map(|value| value.to_string()).unwrap_or_default();
+            let mut cells = vec![team.to_string()];
+            cells.extend((0..innings).map(|inning| show(row.runs_by_inning.get(inning).cloned().flatten())));
+            cells.extend([row.runs, row.hits, row.errors].iter().map(|total| show(*total)));
+            grid.add_row(LinescoreFactory::make_cells(cells, theme.text.clone()));
+        }
+
+        LayoutItem::Layout(Box::new(grid))
+    }
+
+    fn make_cells(contents: Vec<String>, color: Color) -> Vec<LayoutItem> {
+        contents.into_iter()
+            .map(|content| LayoutItem::Widget(WidgetType::Text(Text {
+                content,
+                size: 20,
+                pos: Position::new(Point::origin(), Size::new(0, 0)),
+                color: Color::new(color.r, color.g, color.b)
+            })))
+            .collect()
+    }
+}
+
+pub struct BoxscoreFactory;
+
+impl BoxscoreFactory {
+    const NAME_COLUMN_W: u32 = 220;
+    const STAT_COLUMN_W: u32 = 44;
+    const WIDE_STAT_COLUMN_W: u32 = 60;
+
+    /// Both teams' batting and pitching tables, away team first, scrolling within `size`
+    pub(crate) fn make(boxscore: &crate::data::Boxscore, theme: &Theme, size: Size) -> LayoutItem {
+        let mut scroll = ScrollLayout::new(size);
+        for team in [&boxscore.away, &boxscore.home].iter() {
+            BoxscoreFactory::add_batting(&mut scroll, team, theme);
+            BoxscoreFactory::add_pitching(&mut scroll, team, theme);
+        }
+        LayoutItem::Layout(Box::new(scroll))
+    }
+
+    fn add_batting(scroll: &mut ScrollLayout, team: &crate::data::TeamBoxscore, theme: &Theme) {
+        let mut column_widths = vec![BoxscoreFactory::NAME_COLUMN_W, BoxscoreFactory::STAT_COLUMN_W];
+        column_widths.extend(vec![BoxscoreFactory::STAT_COLUMN_W; 6]);
+        column_widths.push(BoxscoreFactory::WIDE_STAT_COLUMN_W);
+
+        let header = ["Batters", "", "AB", "R", "H", "RBI", "BB", "SO", "AVG"];
+        let rows = team.batters.iter().map(|batter| {
+            let mut cells = vec![batter.name.clone(), batter.position.clone()];
+            cells.extend([batter.at_bats, batter.runs, batter.hits, batter.rbi, batter.walks, batter.strikeouts].iter().map(|stat| stat.to_string()));
+            cells.push(batter.average.clone());
+            cells
+        }).collect();
+
+        scroll.add_table(format!("{} batting", team.name), column_widths, &header, rows, theme);
+    }
+
+    fn add_pitching(scroll: &mut ScrollLayout, team: &crate::data::TeamBoxscore, theme: &Theme) {
+        let mut column_widths = vec![BoxscoreFactory::NAME_COLUMN_W, BoxscoreFactory::STAT_COLUMN_W];
+        column_widths.extend(vec![BoxscoreFactory::STAT_COLUMN_W; 5]);
+        column_widths.push(BoxscoreFactory::WIDE_STAT_COLUMN_W);
+
+        let header = ["Pitchers", "IP", "H", "R", "ER", "BB", "SO", "ERA"];
+        let rows = team.pitchers.iter().map(|pitcher| {
+            let mut cells = vec![pitcher.name.clone(), pitcher.innings_pitched.clone()];
+            cells.extend([pitcher.hits, pitcher.runs, pitcher.earned_runs, pitcher.walks, pitcher.strikeouts].iter().map(|stat| stat.to_string()));
+            cells.push(pitcher.era.clone());
+            cells
+        }).collect();
+
+        scroll.add_table(format!("{} pitching", team.name), column_widths, &header, rows, theme);
+    }
+}
+
+pub struct StandingsFactory;
+
+impl StandingsFactory {
+    /// One table per division, scrolling within `size`
+    pub(crate) fn make(divisions: &[crate::data::DivisionStandings], theme: &Theme, size: Size) -> LayoutItem {
+        let column_widths = vec![220, 44, 44, 60, 50, 60, 60];
+        let header = ["Team", "W", "L", "PCT", "GB", "STRK", "L10"];
+
+        let mut scroll = ScrollLayout::new(size);
+        for division in divisions {
+            let rows = division.teams.iter().map(|team| vec![
+                team.name.clone(),
+                team.wins.to_string(),
+                team.losses.to_string(),
+                team.percentage.clone(),
+                team.games_back.clone(),
+                team.streak.clone(),
+                team.last_ten.map(|(wins, losses)| format!("{}-{}", wins, losses)).unwrap_or_default()
+            ]).collect();
+            scroll.add_table(division.name.clone(), column_widths.clone(), &header, rows, theme);
+        }
+        LayoutItem::Layout(Box::new(scroll))
+    }
+}
+
+pub struct HighlightItemFactory;
+
+impl HighlightItemFactory {
+    pub(crate) const ITEM_SIZE: Size = Size { w: 640, h: 72 };
+    pub(crate) const THUMBNAIL_SIZE: Size = Size { w: 128, h: 72 };
+
+    /// Keyed by the index of the highlight in the game's list
+    pub(crate) fn make(index: usize, highlight: &crate::data::Highlight, thumbnail: Vec<u8>, theme: &Theme) -> ListItem {
+        let unselected = HighlightItemFactory::make_row(highlight, thumbnail.clone(), theme);
+
+        let background = Frame::filled(Position::new(Point::origin(), HighlightItemFactory::ITEM_SIZE), theme.panel.clone());
+        let mut selected = CenteredLayout::new(Position::new(Point::origin(), HighlightItemFactory::ITEM_SIZE));
+        selected.add_child(LayoutItem::Widget(WidgetType::Frame(background)));
+        selected.add_child(HighlightItemFactory::make_row(highlight, thumbnail, theme));
+
+        ListItem::new(index as u64, unselected, LayoutItem::Layout(Box::new(selected)))
+    }
+
+    // The thumbnail next to the title and duration
+    fn make_row(highlight: &crate::data::Highlight, thumbnail: Vec<u8>, theme: &Theme) -> LayoutItem {
+        let image = Image::from_bytes(thumbnail, Position::new(Point::origin(), HighlightItemFactory::THUMBNAIL_SIZE));
+
+        let title = Text {
+            content: highlight.title.clone(),
+            size: 16,
+            pos: Position::new(Point::origin(), Size::new(0, 44)),
+            color: theme.text.clone()
+        };
+
+        let duration = Text {
+            content: highlight.duration.clone().unwrap_or_default(),
+            size: 14,
+            pos: Position::new(Point::origin(), Size::new(0, 20)),
+            color: theme.muted_text.clone()
+        };
+
+        let mut text_layout = VBoxLayout::new();
+        text_layout.add_child(LayoutItem::Widget(WidgetType::Text(title)));
+        text_layout.add_child(LayoutItem::Widget(WidgetType::Text(duration)));
+
+        let text_w = HighlightItemFactory::ITEM_SIZE.w - HighlightItemFactory::THUMBNAIL_SIZE.w;
+        let mut row = GridLayout::new(vec![HighlightItemFactory::THUMBNAIL_SIZE.w, text_w], HighlightItemFactory::ITEM_SIZE.h);
+        row.add_row(vec![LayoutItem::Widget(WidgetType::Image(image)), LayoutItem::Layout(Box::new(text_layout))]);
+
+        LayoutItem::Layout(Box::new(row))
+    }
+}
+
+pub struct PlayItemFactory;
+
+impl PlayItemFactory {
+    pub(crate) const ITEM_SIZE: Size = Size { w: 760, h: 64 };
+
+    /// Keyed by the index of the play, which is its position in the game
+    pub(crate) fn make(index: usize, play: &crate::data::Play, theme: &Theme) -> ListItem {
+        ListItem::new(index as u64, PlayItemFactory::make_lines(play, theme), PlayItemFactory::make_selected_item(play, theme))
+    }
+
+    fn make_selected_item(play: &crate::data::Play, theme: &Theme) -> LayoutItem {
+        let background = Frame::filled(Position::new(Point::origin(), PlayItemFactory::ITEM_SIZE), theme.panel.clone());
+
+        let mut c_layout = CenteredLayout::new(Position::new(Point::origin(), PlayItemFactory::ITEM_SIZE));
+        c_layout.add_child(LayoutItem::Widget(WidgetType::Frame(background)));
+        c_layout.add_child(PlayItemFactory::make_lines(play, theme));
+
+        LayoutItem::Layout(Box::new(c_layout))
+    }
+
+    // The inning and matchup above what happened, with the score after plays that scored
+    fn make_lines(play: &crate::data::Play, theme: &Theme) -> LayoutItem {
+        let matchup = Text {
+            content: format!("{}   {} vs {}", play.inning_label(), play.batter, play.pitcher),
+            size: 16,
+            pos: Position::new(Point::origin(), Size::new(0, 24)),
+            color: theme.muted_text.clone()
+        };
+
+        let result = Text {
+            content: match play.score {
+                Some((away, home)) => { format!("{}   {} - {}", play.description, away, home) }
+                None => { play.description.clone() }
+            },
+            size: 16,
+            pos: Position::new(Point::origin(), Size::new(0, 40)),
+            color: theme.text.clone()
+        };
+
+        let mut vb_layout = VBoxLayout::new();
+        vb_layout.set_position(Position::new(Point::origin(), Size::new(PlayItemFactory::ITEM_SIZE.w, 0)));
+        vb_layout.add_child(LayoutItem::Widget(WidgetType::Text(matchup)));
+        vb_layout.add_child(LayoutItem::Widget(WidgetType::Text(result)));
+
+        LayoutItem::Layout(Box::new(vb_layout))
+    }
 }
 
 pub struct VBoxLayout {
     children: Vec<LayoutItem>,
-    position: Position
+    position: Position,
+    // The index of the child given the height the others leave, the layout keeps its own height if there is one
+    stretched: Option<usize>,
+    dirty: bool
 }
 
 impl VBoxLayout {
     pub fn new() -> Self {
         VBoxLayout {
             children: vec![],
-            position: Position::new(Point::origin(), Size::new(0, 0))
+            position: Position::new(Point::origin(), Size::new(0, 0)),
+            stretched: None,
+            dirty: true
         }
     }
 
@@ -302,16 +808,33 @@ impl VBoxLayout {
         self.position_children();
     }
 
+    /// Adds a child filling the height the other children leave, instead of growing with them
+    pub(crate) fn add_stretched_child(&mut self, item: LayoutItem) {
+        self.stretched = Some(self.children.len());
+        self.add_child(item);
+    }
+
     fn position_children(&mut self) {
+        let stretched = self.stretched;
+        let fixed_h: u32 = self.children.iter().enumerate()
+            .filter(|(index, _)| Some(*index) != stretched)
+            .map(|(_, child)| child.position().size.h)
+            .sum();
         let mut h: i32 = 0;
-        for child in &mut self.children {
+        for (index, child) in self.children.iter_mut().enumerate() {
             let mut new_pos = child.position().clone();
             new_pos.size.w = self.position.size.w;
+            if Some(index) == stretched {
+                new_pos.size.h = self.position.size.h.saturating_sub(fixed_h);
+            }
             new_pos.upper_left = Point::new(0, h);
             child.set_position(new_pos);
             h += child.position().size.h as i32;
         }
-        self.position.size.h = h as u32;
+        if stretched.is_none() {
+            self.position.size.h = h as u32;
+        }
+        self.dirty = true;
     }
 }
 
@@ -319,6 +842,14 @@ impl Layout for VBoxLayout {
     fn child_at(&mut self, index: usize) -> Option<&mut LayoutItem> {
         self.children.get_mut(index)
     }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
 }
 
 impl Responsive for VBoxLayout {
@@ -328,44 +859,60 @@ impl Responsive for VBoxLayout {
 pub struct CenteredLayout {
     children: Vec<LayoutItem>,
     position: Position,
-    center: Point
+    dirty: bool
 }
 
 impl CenteredLayout {
     pub(crate) fn new(position: Position) -> Self {
         CenteredLayout {
             children: vec![],
-            center: Point{x: position.upper_left.x + (position.size.w / 2) as i32, y: position.upper_left.y + (position.size.h / 2) as i32},
-            position
+            position,
+            dirty: true
         }
     }
 
     pub(crate) fn add_child(&mut self, mut item: LayoutItem) {
-        let new_pos = Position {
-            upper_left: Point { x: self.center.x - (item.position().size.w / 2) as i32, y: self.center.y - (item.position().size.h / 2) as i32 },
-            size: item.position().size.clone()
-        };
-        item.set_position(new_pos);
+        item.set_position(CenteredLayout::calculate_position(&self.position, item.position()));
         self.children.push(item);
+        self.dirty = true;
+    }
+
+    // Children keep their size, centered on the layout's middle
+    fn calculate_position(layout: &Position, item: &Position) -> Position {
+        Position {
+            upper_left: Point { x: (layout.size.w / 2) as i32 - (item.size.w / 2) as i32, y: (layout.size.h / 2) as i32 - (item.size.h / 2) as i32 },
+            size: item.size.clone()
+        }
+    }
+
+    fn position_children(&mut self) {
+        let position = self.position.clone();
+        for child in &mut self.children {
+            child.set_position(CenteredLayout::calculate_position(&position, child.position()));
+        }
+        self.dirty = true;
     }
 }
 
 pub(crate) struct VCenteredLayout {
     children: Vec<LayoutItem>,
     position: Position,
+    dirty: bool
 }
 
 impl VCenteredLayout {
     pub(crate) fn new(position: Position) -> Self {
         VCenteredLayout {
             children: vec![],
-            position
+            position,
+            dirty: true
         }
     }
 
     pub(crate) fn add_child(&mut self, mut item: LayoutItem) {
         item.set_position(VCenteredLayout::calculate_position(self.position(), item.position()));
         self.children.push(item);
+        self.dirty = true;
     }
 
     fn calculate_position(layout: &Position, item: &Position) -> Position {
@@ -380,6 +927,174 @@ impl VCenteredLayout {
         for child in &mut self.children {
             child.set_position(VCenteredLayout::calculate_position(&position, child.position()));
         }
+        self.dirty = true;
+    }
+}
+
+/// Layers its children on top of each other, each one filling the layout
+pub(crate) struct StackLayout {
+    children: Vec<LayoutItem>,
+    position: Position,
+    dirty: bool
+}
+
+impl StackLayout {
+    pub(crate) fn new(position: Position) -> Self {
+        StackLayout {
+            children: vec![],
+            position,
+            dirty: true
+        }
+    }
+
+    pub(crate) fn add_child(&mut self, mut item: LayoutItem) {
+        item.set_position(Position::new(Point::origin(), self.position.size.clone()));
+        self.children.push(item);
+        self.dirty = true;
+    }
+}
+
+/// Draws an outline around its content, both sized to the layout
+pub(crate) struct FramedLayout {
+    children: Vec<LayoutItem>,
+    position: Position,
+    dirty: bool
+}
+
+impl FramedLayout {
+    pub(crate) fn new(content: LayoutItem, color: Color) -> Self {
+        let position = content.position().clone();
+        let frame = Frame::outlined(Position::new(Point::origin(), position.size.clone()), color);
+        FramedLayout {
+            children: vec![content, LayoutItem::Widget(WidgetType::Frame(frame))],
+            position,
+            dirty: true
+        }
+    }
+}
+
+/// Lays children out in rows of cells, each column with its own width
+pub(crate) struct GridLayout {
+    children: Vec<LayoutItem>,
+    position: Position,
+    column_widths: Vec<u32>,
+    row_height: u32,
+    dirty: bool
+}
+
+impl GridLayout {
+    pub(crate) fn new(column_widths: Vec<u32>, row_height: u32) -> Self {
+        GridLayout {
+            children: vec![],
+            position: Position::new(Point::origin(), Size::new(column_widths.iter().sum(), 0)),
+            column_widths,
+            row_height,
+            dirty: true
+        }
+    }
+
+    /// Appends a row, cells past the last column are dropped
+    pub(crate) fn add_row(&mut self, cells: Vec<LayoutItem>) {
+        let y = self.position.size.h as i32;
+        let mut x: i32 = 0;
+        for (mut cell, w) in cells.into_iter().zip(self.column_widths.iter()) {
+            cell.set_position(Position::new(Point::new(x, y), Size::new(*w, self.row_height)));
+            self.children.push(cell);
+            x += *w as i32;
+        }
+        self.position.size.h += self.row_height;
+        self.dirty = true;
+    }
+}
+
+const TABLE_TITLE_H: u32 = 40;
+const TABLE_ROW_H: u32 = 28;
+
+/// Stacks children vertically and shows as many of them as fit, scrolling one child at a time
+pub(crate) struct ScrollLayout {
+    children: Vec<LayoutItem>,
+    position: Position,
+    first: usize,
+    dirty: bool
+}
+
+impl ScrollLayout {
+    pub(crate) fn new(size: Size) -> Self {
+        ScrollLayout {
+            children: vec![],
+            position: Position::new(Point::origin(), size),
+            first: 0,
+            dirty: true
+        }
+    }
+
+    pub(crate) fn add_child(&mut self, item: LayoutItem) {
+        self.children.push(item);
+        self.position_children();
+    }
+
+    /// The number of children from the first shown one on that fit
+    fn visible_count(&self) -> usize {
+        let mut h = 0;
+        self.children[self.first..].iter()
+            .take_while(|child| {
+                h += child.position().size.h;
+                h <= self.position.size.h
+            })
+            .count()
+    }
+
+    /// Appends a titled table, every row its own one row grid so that the table scrolls row by row
+    pub(crate) fn add_table(&mut self, title: String, column_widths: Vec<u32>, header: &[&str], rows: Vec<Vec<String>>, theme: &Theme) {
+        let width = column_widths.iter().sum();
+        self.add_child(LayoutItem::Widget(WidgetType::Text(Text {
+            content: title,
+            size: 24,
+            pos: Position::new(Point::origin(), Size::new(width, TABLE_TITLE_H)),
+            color: theme.text.clone()
+        })));
+
+        let header = header.iter().map(|cell| cell.to_string()).collect();
+        let rows = std::iter::once((header, theme.muted_text.clone()))
+            .chain(rows.into_iter().map(|row| (row, theme.text.clone())));
+        for (cells, color) in rows {
+            let mut grid = GridLayout::new(column_widths.clone(), TABLE_ROW_H);
+            grid.add_row(cells.into_iter()
+                .map(|content| LayoutItem::Widget(WidgetType::Text(Text {
+                    content,
+                    size: 18,
+                    pos: Position::new(Point::origin(), Size::new(0, 0)),
+                    color: Color::new(color.r, color.g, color.b)
+                })))
+                .collect());
+            self.add_child(LayoutItem::Layout(Box::new(grid)));
+        }
+    }
+
+    fn scroll_down(&mut self) -> bool {
+        if self.first + self.visible_count() < self.children.len() {
+            self.first += 1;
+            true
+        } else { false }
+    }
+
+    fn scroll_up(&mut self) -> bool {
+        if self.first > 0 {
+            self.first -= 1;
+            true
+        } else { false }
+    }
+
+    // Children narrower than the layout are centered, the hidden ones keep their last position
+    fn position_children(&mut self) {
+        let mut h: i32 = 0;
+        let w = self.position.size.w;
+        for child in &mut self.children[self.first..] {
+            let size = child.position().size.clone();
+            child.set_position(Position::new(Point::new((w.saturating_sub(size.w) / 2) as i32, h), size));
+            h += child.position().size.h as i32;
+        }
+        self.dirty = true;
     }
 }
 
@@ -433,9 +1148,7 @@ impl Positionable for Text {
 impl Positionable for Image {
     fn set_position(&mut self, pos: Position) {
         if self.preserve_aspect_w {
-            let aspect = 16.0 / 9.0;
-            let new_size = Size::new(pos.size.w, (pos.size.w as f32 / aspect) as u32);
-            self.pos = Position::new(pos.upper_left, new_size);
+            self.pos = Position::new(pos.upper_left, Image::size_for_width(pos.size.w));
         } else {
             self.pos = pos;
         }
@@ -484,11 +1197,12 @@ impl Positionable for ListItem {
     fn set_position(&mut self, pos: Position) {
         let mut scaled_pos = pos.clone();
         let center = pos.center();
-        scaled_pos.size = Size::new((pos.size.w as f32 * 0.75) as u32, (pos.size.h as f32 * 0.75) as u32);
+        scaled_pos.size = Size::new((pos.size.w as f32 * self.scale) as u32, (pos.size.h as f32 * self.scale) as u32);
         scaled_pos.upper_left.x = center.x - (scaled_pos.size.w / 2) as i32;
         scaled_pos.upper_left.y = center.y - (scaled_pos.size.h / 2) as i32;
         self.item.set_position(scaled_pos);
-        self.selected_item.set_position(pos);
+        self.selected_item.set_position(pos.clone());
+        self.pos = pos;
     }
 
     fn position(&self) -> &Position {
@@ -499,6 +1213,7 @@ impl Positionable for ListItem {
 impl Positionable for CenteredLayout {
     fn set_position(&mut self, pos: Position) {
         self.position = pos;
+        self.position_children();
     }
 
     fn position(&self) -> &Position {
@@ -517,9 +1232,74 @@ impl Positionable for VCenteredLayout {
     }
 }
 
+impl Positionable for StackLayout {
+    fn set_position(&mut self, pos: Position) {
+        for child in &mut self.children {
+            child.set_position(Position::new(Point::origin(), pos.size.clone()));
+        }
+        self.position = pos;
+        self.dirty = true;
+    }
+
+    fn position(&self) -> &Position {
+        &self.position
+    }
+}
+
+impl Positionable for FramedLayout {
+    fn set_position(&mut self, pos: Position) {
+        for child in &mut self.children {
+            child.set_position(Position::new(Point::origin(), pos.size.clone()));
+        }
+        self.position = pos;
+        self.dirty = true;
+    }
+
+    fn position(&self) -> &Position {
+        &self.position
+    }
+}
+
+impl Positionable for GridLayout {
+    fn set_position(&mut self, pos: Position) {
+        // The cells keep their own sizes, only the grid moves
+        self.position.upper_left = pos.upper_left;
+        self.dirty = true;
+    }
+
+    fn position(&self) -> &Position {
+        &self.position
+    }
+}
+
+impl Positionable for ScrollLayout {
+    fn set_position(&mut self, pos: Position) {
+        self.position = pos;
+        self.position_children();
+    }
+
+    fn position(&self) -> &Position {
+        &self.position
+    }
+}
+
+impl Positionable for VListLayout {
+    fn set_position(&mut self, pos: Position) {
+        // Items keep their size, a new height shows more or fewer of them around the selected one
+        self.position = pos;
+        let selected = self.selected;
+        self.select(selected);
+    }
+
+    fn position(&self) -> &Position {
+        &self.position
+    }
+}
+
 impl Positionable for ListLayout {
     fn set_position(&mut self, pos: Position) {
         self.position = pos;
+        self.center_selected();
     }
 
     fn position(&self) -> &Position {
@@ -527,26 +1307,185 @@ impl Positionable for ListLayout {
     }
 }
 
+/// Widgets do not change once built, so a layout counts as changed when it moved or its children changed
 pub trait Layout: Positionable + Responsive {
     fn child_at(&mut self, index: usize) -> Option<&mut LayoutItem>;
+
+    /// Whether the layout changed since it was last drawn, not looking at the layouts below it
+    fn is_dirty(&self) -> bool;
+
+    fn mark_clean(&mut self);
+
+    /// The layout as a list of keyed items, for the layouts that are one
+    fn as_item_list(&mut self) -> Option<&mut dyn ItemList> {
+        None
+    }
+}
+
+/// Layouts listing `ListItem`s told apart by their keys, one of which is selected
+pub trait ItemList {
+    /// The item with `key` for changing it in place, which redraws the list
+    fn item_mut(&mut self, key: u64) -> Option<&mut ListItem>;
+
+    /// Swaps `item` in for the child with the same key, handing it back if this list has no such child
+    fn replace_item(&mut self, item: ListItem) -> Option<ListItem> {
+        match self.item_mut(item.key) {
+            None => Some(item),
+            Some(child) => {
+                child.replace_with(item);
+                None
+            }
+        }
+    }
+
+    fn selected_key(&self) -> Option<u64>;
+
+    /// Moves the selection to the child with `key`, telling whether this list has one
+    fn select_key(&mut self, key: u64) -> bool;
 }
 
 impl Layout for ListItem {
     fn child_at(&mut self, _: usize) -> Option<&mut LayoutItem> {
         unimplemented!()
     }
+
+    // Items are drawn through their list, which tracks their changes
+    fn is_dirty(&self) -> bool {
+        false
+    }
+
+    fn mark_clean(&mut self) {}
 }
 
 impl Layout for CenteredLayout {
     fn child_at(&mut self, index: usize) -> Option<&mut LayoutItem> {
         self.children.get_mut(index)
     }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
 }
 
 impl Layout for VCenteredLayout {
     fn child_at(&mut self, index: usize) -> Option<&mut LayoutItem> {
         self.children.get_mut(index)
     }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
+}
+
+impl Layout for StackLayout {
+    fn child_at(&mut self, index: usize) -> Option<&mut LayoutItem> {
+        self.children.get_mut(index)
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
+}
+
+impl Layout for FramedLayout {
+    fn child_at(&mut self, index: usize) -> Option<&mut LayoutItem> {
+        self.children.get_mut(index)
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
+}
+
+impl Layout for GridLayout {
+    fn child_at(&mut self, index: usize) -> Option<&mut LayoutItem> {
+        self.children.get_mut(index)
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
+}
+
+impl Layout for ScrollLayout {
+    fn child_at(&mut self, index: usize) -> Option<&mut LayoutItem> {
+        if index < self.visible_count() {
+            self.children.get_mut(self.first + index)
+        } else { None }
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
+}
+
+impl Layout for VListLayout {
+    fn child_at(&mut self, index: usize) -> Option<&mut LayoutItem> {
+        if index >= self.visible_count() {
+            return None;
+        }
+        let index = self.first + index;
+        let selected = self.selected;
+        self.children.get_mut(index).map(|item| if index == selected { &mut item.selected_item } else { &mut item.item })
+    }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
+
+    fn as_item_list(&mut self) -> Option<&mut dyn ItemList> {
+        Some(self)
+    }
+}
+
+impl ItemList for VListLayout {
+    fn item_mut(&mut self, key: u64) -> Option<&mut ListItem> {
+        let item = self.children.iter_mut().find(|child| child.key == key);
+        self.dirty |= item.is_some();
+        item
+    }
+
+    fn selected_key(&self) -> Option<u64> {
+        self.children.get(self.selected).map(|child| child.key)
+    }
+
+    fn select_key(&mut self, key: u64) -> bool {
+        match self.children.iter().position(|child| child.key == key) {
+            None => false,
+            Some(index) => {
+                self.select(index);
+                true
+            }
+        }
+    }
 }
 
 impl Layout for ListLayout {
@@ -559,6 +1498,41 @@ impl Layout for ListLayout {
             }
         }
     }
+
+    fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    fn mark_clean(&mut self) {
+        self.dirty = false;
+    }
+
+    fn as_item_list(&mut self) -> Option<&mut dyn ItemList> {
+        Some(self)
+    }
+}
+
+impl ItemList for ListLayout {
+    fn item_mut(&mut self, key: u64) -> Option<&mut ListItem> {
+        let item = self.children.iter_mut().find(|child| child.key == key);
+        self.dirty |= item.is_some();
+        item
+    }
+
+    fn selected_key(&self) -> Option<u64> {
+        self.children.get(self.selected).map(|child| child.key)
+    }
+
+    fn select_key(&mut self, key: u64) -> bool {
+        match self.children.iter().position(|child| child.key == key) {
+            None => false,
+            Some(index) => {
+                self.selected = index;
+                self.center_selected();
+                true
+            }
+        }
+    }
 }
 
 pub trait Responsive {
@@ -576,6 +1550,7 @@ impl Responsive for ListLayout {
         match key {
             Key::Right => { self.select_next(); }
             Key::Left => { self.select_prev(); }
+            _ => { return false }
         }
         true
     }
@@ -593,6 +1568,62 @@ impl Responsive for VCenteredLayout {
     }
 }
 
+impl Responsive for VListLayout {
+    fn handle_key(&mut self, key: Key) -> bool {
+        let last = self.children.len().saturating_sub(1);
+        let page = self.visible_count();
+        let selected = match key {
+            Key::Down => { (self.selected + 1).min(last) }
+            Key::Up => { self.selected.saturating_sub(1) }
+            Key::PageDown => { (self.selected + page).min(last) }
+            Key::PageUp => { self.selected.saturating_sub(page) }
+            _ => { return false }
+        };
+        self.select(selected);
+        true
+    }
+}
+
+impl Responsive for ScrollLayout {
+    fn handle_key(&mut self, key: Key) -> bool {
+        let scrolled = match key {
+            Key::Down => { self.scroll_down() }
+            Key::Up => { self.scroll_up() }
+            Key::PageDown => {
+                let page = self.visible_count().max(1);
+                (0..page).fold(false, |scrolled, _| self.scroll_down() || scrolled)
+            }
+            Key::PageUp => {
+                let page = self.visible_count().max(1);
+                (0..page).fold(false, |scrolled, _| self.scroll_up() || scrolled)
+            }
+            _ => { return false }
+        };
+        if scrolled {
+            self.position_children();
+        }
+        true
+    }
+}
+
+impl Responsive for StackLayout {
+    fn handle_key(&mut self, _: Key) -> bool {
+        false
+    }
+}
+
+impl Responsive for FramedLayout {
+    fn handle_key(&mut self, _: Key) -> bool {
+        false
+    }
+}
+
+impl Responsive for GridLayout {
+    fn handle_key(&mut self, _: Key) -> bool {
+        false
+    }
+}
+
 pub(crate) fn translate_to_global(relative: &Point, global: &Point) -> Point {
     Point {
         x: global.x + relative.x,
@@ -621,6 +1652,213 @@ pub trait Visitor<T> {
     fn visit_element(&mut self, element: &mut T);
 }
 
+/// Walks a layout tree until it finds the list holding the replaced item's key
+pub struct ListItemReplacer {
+    item: Option<ListItem>
+}
+
+impl ListItemReplacer {
+    pub fn new(item: ListItem) -> Self {
+        ListItemReplacer { item: Some(item) }
+    }
+}
+
+impl Visitor<LayoutItem> for ListItemReplacer {
+    fn visit_element(&mut self, element: &mut LayoutItem) {
+        if let LayoutItem::Layout(layout) = element {
+            self.visit_element(layout)
+        }
+    }
+}
+
+impl Visitor<Box<dyn Layout>> for ListItemReplacer {
+    fn visit_element(&mut self, element: &mut Box<dyn Layout>) {
+        if let Some(list) = element.as_item_list() {
+            if let Some(item) = self.item.take() {
+                self.item = list.replace_item(item);
+            }
+        }
+
+        let mut i = 0;
+        while self.item.is_some() {
+            match element.child_at(i) {
+                Some(child) => { self.visit_element(child) }
+                None => { break }
+            }
+            i += 1;
+        }
+    }
+}
+
+/// Walks a layout tree until it finds the list holding the item with `key` and shows new images on that item
+pub struct ItemImageSetter {
+    key: u64,
+    images: Option<(Vec<u8>, Vec<u8>)>
+}
+
+impl ItemImageSetter {
+    /// `unselected` is shown in the item's unselected look, `selected` in its selected one
+    pub fn new(key: u64, unselected: Vec<u8>, selected: Vec<u8>) -> Self {
+        ItemImageSetter { key, images: Some((unselected, selected)) }
+    }
+}
+
+impl Visitor<LayoutItem> for ItemImageSetter {
+    fn visit_element(&mut self, element: &mut LayoutItem) {
+        if let LayoutItem::Layout(layout) = element {
+            self.visit_element(layout)
+        }
+    }
+}
+
+impl Visitor<Box<dyn Layout>> for ItemImageSetter {
+    fn visit_element(&mut self, element: &mut Box<dyn Layout>) {
+        if let Some(item) = element.as_item_list().and_then(|list| list.item_mut(self.key)) {
+            if let Some((unselected, selected)) = self.images.take() {
+                item.set_images(unselected, selected);
+            }
+        }
+
+        let mut i = 0;
+        while self.images.is_some() {
+            match element.child_at(i) {
+                Some(child) => { self.visit_element(child) }
+                None => { break }
+            }
+            i += 1;
+        }
+    }
+}
+
+// Shows `data` in every image of a tree
+struct ImageSetter {
+    data: Vec<u8>
+}
+
+impl Visitor<LayoutItem> for ImageSetter {
+    fn visit_element(&mut self, element: &mut LayoutItem) {
+        match element {
+            LayoutItem::Layout(layout) => { self.visit_element(layout) }
+            LayoutItem::Widget(WidgetType::Image(image)) => { image.set_bytes(self.data.clone()) }
+            LayoutItem::Widget(_) => {}
+        }
+    }
+}
+
+impl Visitor<Box<dyn Layout>> for ImageSetter {
+    fn visit_element(&mut self, element: &mut Box<dyn Layout>) {
+        let mut i = 0;
+        while let Some(child) = element.child_at(i) {
+            self.visit_element(child);
+            i += 1;
+        }
+    }
+}
+
+/// Walks a layout tree until it finds a layout with a selection and records the selected key
+pub struct SelectedKeyFinder {
+    pub(crate) key: Option<u64>
+}
+
+impl SelectedKeyFinder {
+    pub fn new() -> Self {
+        SelectedKeyFinder { key: None }
+    }
+}
+
+impl Visitor<LayoutItem> for SelectedKeyFinder {
+    fn visit_element(&mut self, element: &mut LayoutItem) {
+        if let LayoutItem::Layout(layout) = element {
+            self.visit_element(layout)
+        }
+    }
+}
+
+impl Visitor<Box<dyn Layout>> for SelectedKeyFinder {
+    fn visit_element(&mut self, element: &mut Box<dyn Layout>) {
+        self.key = element.as_item_list().and_then(|list| list.selected_key());
+
+        let mut i = 0;
+        while self.key.is_none() {
+            match element.child_at(i) {
+                Some(child) => { self.visit_element(child) }
+                None => { break }
+            }
+            i += 1;
+        }
+    }
+}
+
+/// Walks a layout tree until it finds a list with the key and selects it there,
+/// such as to keep the selection of a scene that was made anew
+pub struct KeySelector {
+    key: u64,
+    pub(crate) selected: bool
+}
+
+impl KeySelector {
+    pub fn new(key: u64) -> Self {
+        KeySelector { key, selected: false }
+    }
+}
+
+impl Visitor<LayoutItem> for KeySelector {
+    fn visit_element(&mut self, element: &mut LayoutItem) {
+        if let LayoutItem::Layout(layout) = element {
+            self.visit_element(layout)
+        }
+    }
+}
+
+impl Visitor<Box<dyn Layout>> for KeySelector {
+    fn visit_element(&mut self, element: &mut Box<dyn Layout>) {
+        self.selected = element.as_item_list().is_some_and(|list| list.select_key(self.key));
+
+        let mut i = 0;
+        while !self.selected {
+            match element.child_at(i) {
+                Some(child) => { self.visit_element(child) }
+                None => { break }
+            }
+            i += 1;
+        }
+    }
+}
+
+/// Walks a layout tree until it finds a layout that changed since it was last drawn
+pub struct DirtyFinder {
+    pub(crate) dirty: bool
+}
+
+impl DirtyFinder {
+    pub fn new() -> Self {
+        DirtyFinder { dirty: false }
+    }
+}
+
+impl Visitor<LayoutItem> for DirtyFinder {
+    fn visit_element(&mut self, element: &mut LayoutItem) {
+        if let LayoutItem::Layout(layout) = element {
+            self.visit_element(layout)
+        }
+    }
+}
+
+impl Visitor<Box<dyn Layout>> for DirtyFinder {
+    fn visit_element(&mut self, element: &mut Box<dyn Layout>) {
+        self.dirty = element.is_dirty();
+
+        let mut i = 0;
+        while !self.dirty {
+            match element.child_at(i) {
+                Some(child) => { self.visit_element(child) }
+                None => { break }
+            }
+            i += 1;
+        }
+    }
+}
+
 trait Widget: Positionable {
     fn get_type(&self) -> WidgetType;
 }
\ No newline at end of file